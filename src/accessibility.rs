@@ -0,0 +1,158 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::components::*;
+use crate::enemies::ENEMY_ROSTER;
+use crate::settings::Settings;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Palette {
+    Default,
+    Deuteranopia,
+    Protanopia,
+    Tritanopia,
+}
+
+impl Palette {
+    pub fn next(&self) -> Palette {
+        match self {
+            Palette::Default => Palette::Deuteranopia,
+            Palette::Deuteranopia => Palette::Protanopia,
+            Palette::Protanopia => Palette::Tritanopia,
+            Palette::Tritanopia => Palette::Default,
+        }
+    }
+
+    /// Overworld colour for the enemy in a given room. The colourblind sets
+    /// are picked from the Okabe-Ito palette so neighbours stay distinct.
+    pub fn enemy_color(&self, room_index: usize) -> Color {
+        let table: [Color; TOTAL_ROOMS] = match self {
+            Palette::Default => return ENEMY_ROSTER[room_index].color,
+            Palette::Deuteranopia | Palette::Protanopia => [
+                Color::srgb(0.90, 0.62, 0.0),
+                Color::srgb(0.34, 0.71, 0.91),
+                Color::srgb(0.0, 0.45, 0.70),
+                Color::srgb(0.94, 0.89, 0.26),
+                Color::srgb(0.80, 0.47, 0.65),
+                Color::srgb(1.0, 1.0, 1.0),
+            ],
+            Palette::Tritanopia => [
+                Color::srgb(0.84, 0.37, 0.0),
+                Color::srgb(0.0, 0.62, 0.45),
+                Color::srgb(0.80, 0.47, 0.65),
+                Color::srgb(1.0, 0.6, 0.6),
+                Color::srgb(0.6, 0.2, 0.2),
+                Color::srgb(1.0, 1.0, 1.0),
+            ],
+        };
+        table[room_index % TOTAL_ROOMS]
+    }
+
+    pub fn positive(&self) -> Color {
+        match self {
+            Palette::Default => Color::srgb(0.3, 1.0, 0.3),
+            Palette::Deuteranopia | Palette::Protanopia => Color::srgb(0.34, 0.71, 0.91),
+            Palette::Tritanopia => Color::srgb(0.0, 0.62, 0.45),
+        }
+    }
+
+    pub fn danger(&self) -> Color {
+        match self {
+            Palette::Default => Color::srgb(1.0, 0.3, 0.3),
+            Palette::Deuteranopia | Palette::Protanopia => Color::srgb(0.90, 0.62, 0.0),
+            Palette::Tritanopia => Color::srgb(0.84, 0.37, 0.0),
+        }
+    }
+
+    pub fn bullet(&self) -> Color {
+        match self {
+            Palette::Default => Color::srgb(1.0, 0.95, 0.2),
+            Palette::Deuteranopia | Palette::Protanopia | Palette::Tritanopia => Color::srgb(1.0, 1.0, 1.0),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AssistMode {
+    Off,
+    SlowBullets,
+    InfiniteHp,
+}
+
+impl AssistMode {
+    pub fn next(&self) -> AssistMode {
+        match self {
+            AssistMode::Off => AssistMode::SlowBullets,
+            AssistMode::SlowBullets => AssistMode::InfiniteHp,
+            AssistMode::InfiniteHp => AssistMode::Off,
+        }
+    }
+
+    pub fn bullet_speed(&self) -> f32 {
+        if *self == AssistMode::SlowBullets { 0.6 } else { 1.0 }
+    }
+}
+
+#[derive(Component)]
+pub struct BulletOutline;
+
+/// Restyles freshly spawned bullets for the active palette and contrast mode.
+pub fn style_new_bullets(
+    mut commands: Commands,
+    settings: Res<Settings>,
+    mut bullets: Query<(Entity, &Bullet, &mut Sprite), Added<Bullet>>,
+) {
+    for (entity, bullet, mut sprite) in bullets.iter_mut() {
+        if bullet.status.is_none() {
+            sprite.color = settings.palette.bullet();
+        }
+
+        if settings.high_contrast_bullets {
+            sprite.custom_size = Some(Vec2::new(22.0, 22.0));
+            commands.entity(entity).with_children(|parent| {
+                parent.spawn((
+                    Sprite {
+                        color: Color::BLACK,
+                        custom_size: Some(Vec2::new(32.0, 32.0)),
+                        ..default()
+                    },
+                    Transform::from_translation(Vec3::new(0.0, 0.0, -0.05)),
+                    BulletOutline,
+                ));
+            });
+        }
+    }
+}
+
+pub fn recolor_enemies(
+    settings: Res<Settings>,
+    battle_state: Res<CurrentBattle>,
+    mut enemies: Query<(&Enemy, &mut Sprite)>,
+    mut battle_sprites: Query<&mut Sprite, (With<EnemySprite>, Without<Enemy>)>,
+) {
+    for (enemy, mut sprite) in enemies.iter_mut() {
+        sprite.color = settings.palette.enemy_color(enemy.room_index);
+    }
+
+    // The arena copy of the current enemy follows the same palette
+    let Ok((enemy, _)) = enemies.get(battle_state.enemy_entity) else { return };
+    let color = settings.palette.enemy_color(enemy.room_index);
+    for mut sprite in battle_sprites.iter_mut() {
+        sprite.color = color;
+    }
+}
+
+/// Keeps the soul above zero while the infinite HP assist is active.
+pub fn apply_assist_health(settings: Res<Settings>, mut souls: Query<&mut Player, With<PlayerSprite>>) {
+    if settings.assist != AssistMode::InfiniteHp {
+        return;
+    }
+    for mut player in souls.iter_mut() {
+        player.health = player.health.max(1);
+    }
+}
+
+pub fn mark_assisted_run(settings: Res<Settings>, mut game_progress: ResMut<GameProgress>) {
+    if settings.assist != AssistMode::Off && !game_progress.assisted {
+        game_progress.assisted = true;
+    }
+}
@@ -0,0 +1,292 @@
+use std::collections::{HashMap, HashSet};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::components::*;
+use crate::difficulty::{Difficulty, DifficultyPreset};
+use crate::events::*;
+
+const ACHIEVEMENTS_PATH: &str = "achievements.ron";
+const PROGRESS_PATH: &str = "achievement_progress.ron";
+const TOAST_SECS: f32 = 3.5;
+
+/// What advances an achievement. Streak-style goals track the best value reached,
+/// the rest count up over every run.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub enum AchievementGoal {
+    UntouchedRoomClear,
+    PerfectStreak,
+    /// Finish the gauntlet at a difficulty at least as punishing as this preset.
+    WinOn(DifficultyPreset),
+    SpareEnemies,
+    Grazes,
+    BattlesWon,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AchievementDef {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub goal: AchievementGoal,
+    pub target: u32,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AchievementRecords {
+    pub progress: HashMap<String, u32>,
+    pub unlocked: HashSet<String>,
+}
+
+#[derive(Resource, Default)]
+pub struct Achievements {
+    pub defs: Vec<AchievementDef>,
+    pub records: AchievementRecords,
+    /// Names unlocked since the last toast pass.
+    pending: Vec<String>,
+    dirty: bool,
+}
+
+impl Achievements {
+    pub fn load() -> Self {
+        let defs = crate::persist::load_asset_ron(ACHIEVEMENTS_PATH);
+        let records = crate::persist::load_ron(PROGRESS_PATH);
+        Self { defs, records, pending: Vec::new(), dirty: false }
+    }
+
+    pub fn save(&self) {
+        crate::persist::save_ron(PROGRESS_PATH, &self.records);
+    }
+
+    pub fn progress(&self, def: &AchievementDef) -> u32 {
+        self.records.progress.get(&def.id).copied().unwrap_or(0).min(def.target)
+    }
+
+    pub fn is_unlocked(&self, def: &AchievementDef) -> bool {
+        self.records.unlocked.contains(&def.id)
+    }
+
+    /// Adds to every counter whose goal matches.
+    fn add(&mut self, matches: impl Fn(AchievementGoal) -> bool, amount: u32) {
+        self.update(matches, |progress| progress + amount);
+    }
+
+    /// Raises every matching counter to `value` if it is a new best.
+    fn reach(&mut self, matches: impl Fn(AchievementGoal) -> bool, value: u32) {
+        self.update(matches, |progress| progress.max(value));
+    }
+
+    fn update(&mut self, matches: impl Fn(AchievementGoal) -> bool, step: impl Fn(u32) -> u32) {
+        for def in self.defs.iter().filter(|def| matches(def.goal)) {
+            if self.records.unlocked.contains(&def.id) {
+                continue;
+            }
+            let progress = self.records.progress.entry(def.id.clone()).or_insert(0);
+            *progress = step(*progress);
+            self.dirty = true;
+            if *progress >= def.target {
+                self.records.unlocked.insert(def.id.clone());
+                self.pending.push(def.name.clone());
+            }
+        }
+    }
+}
+
+/// Per-run bookkeeping the counters are derived from.
+#[derive(Resource, Default)]
+pub struct AchievementTracker {
+    pub hit_this_battle: bool,
+    pub perfect_streak: u32,
+    pub spared: HashSet<usize>,
+    /// The player lost a battle at some point this run.
+    pub fell: bool,
+}
+
+#[derive(Component)]
+pub struct Toast {
+    timer: Timer,
+}
+
+#[derive(Component)]
+pub struct AchievementsMenuUI;
+
+#[allow(clippy::too_many_arguments)]
+pub fn track_achievements(
+    mut started: MessageReader<BattleStarted>,
+    mut player_damaged: MessageReader<PlayerDamaged>,
+    mut rated: MessageReader<AttackRated>,
+    mut grazed: MessageReader<BulletGrazed>,
+    mut ended: MessageReader<BattleEnded>,
+    mut cleared: MessageReader<RoomCleared>,
+    mut completed: MessageReader<GauntletCompleted>,
+    enemy_query: Query<&Enemy>,
+    difficulty: Res<Difficulty>,
+    game_progress: Res<GameProgress>,
+    mut tracker: ResMut<AchievementTracker>,
+    mut achievements: ResMut<Achievements>,
+) {
+    // Assist tools make these goals easier, so assisted runs don't count toward them
+    if game_progress.assisted {
+        started.clear();
+        player_damaged.clear();
+        rated.clear();
+        grazed.clear();
+        ended.clear();
+        cleared.clear();
+        completed.clear();
+        return;
+    }
+
+    if started.read().count() > 0 {
+        tracker.hit_this_battle = false;
+    }
+    if player_damaged.read().count() > 0 {
+        tracker.hit_this_battle = true;
+    }
+
+    for event in rated.read() {
+        tracker.perfect_streak = if event.rating == Rating::Perfect { tracker.perfect_streak + 1 } else { 0 };
+        let streak = tracker.perfect_streak;
+        achievements.reach(|goal| goal == AchievementGoal::PerfectStreak, streak);
+    }
+
+    let grazes = grazed.read().count() as u32;
+    if grazes > 0 {
+        achievements.add(|goal| goal == AchievementGoal::Grazes, grazes);
+    }
+
+    for event in ended.read() {
+        match event.outcome {
+            BattleOutcome::Won => achievements.add(|goal| goal == AchievementGoal::BattlesWon, 1),
+            BattleOutcome::Spared => {
+                if let Ok(enemy) = enemy_query.get(event.enemy) {
+                    tracker.spared.insert(enemy.room_index);
+                }
+            }
+            BattleOutcome::Lost => tracker.fell = true,
+            BattleOutcome::Fled => {}
+        }
+    }
+
+    for _ in cleared.read() {
+        if !tracker.hit_this_battle {
+            achievements.add(|goal| goal == AchievementGoal::UntouchedRoomClear, 1);
+        }
+    }
+
+    for _ in completed.read() {
+        // Only a finished run where the player never fell counts toward sparing every enemy
+        if !tracker.fell {
+            let spared = tracker.spared.len() as u32;
+            achievements.reach(|goal| goal == AchievementGoal::SpareEnemies, spared);
+        }
+
+        let multiplier = difficulty.score_multiplier();
+        achievements.add(
+            |goal| match goal {
+                AchievementGoal::WinOn(preset) => multiplier >= Difficulty::preset(preset).score_multiplier(),
+                _ => false,
+            },
+            1,
+        );
+    }
+
+    if achievements.dirty {
+        achievements.dirty = false;
+        achievements.save();
+    }
+}
+
+/// Turns fresh unlocks into small toasts stacked down the top-right corner.
+pub fn spawn_achievement_toasts(
+    mut commands: Commands,
+    mut achievements: ResMut<Achievements>,
+    toasts: Query<(), With<Toast>>,
+) {
+    if achievements.pending.is_empty() {
+        return;
+    }
+
+    let first_slot = toasts.iter().count();
+    for (slot, name) in (first_slot..).zip(std::mem::take(&mut achievements.pending)) {
+        spawn_toast(&mut commands, slot, "ACHIEVEMENT UNLOCKED", name);
+    }
+}
+
+/// Spawns one toast in the given stacking slot; shared with quest updates.
+pub fn spawn_toast(commands: &mut Commands, slot: usize, header: &str, body: String) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(16.0 + slot as f32 * 64.0),
+                right: Val::Px(16.0),
+                padding: UiRect::all(Val::Px(10.0)),
+                flex_direction: FlexDirection::Column,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.1, 0.1, 0.15, 0.85)),
+            GlobalZIndex(30),
+            Toast { timer: Timer::from_seconds(TOAST_SECS, TimerMode::Once) },
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(header),
+                TextFont { font_size: 12.0, ..default() },
+                TextColor(Color::srgb(1.0, 0.85, 0.3)),
+            ));
+            parent.spawn((
+                Text::new(body),
+                TextFont { font_size: 18.0, ..default() },
+                TextColor(Color::WHITE),
+            ));
+        });
+}
+
+pub fn update_achievement_toasts(
+    mut commands: Commands,
+    time: Res<Time<Real>>,
+    mut toasts: Query<(Entity, &mut Toast, &mut BackgroundColor)>,
+) {
+    for (entity, mut toast, mut background) in toasts.iter_mut() {
+        toast.timer.tick(time.delta());
+        if toast.timer.is_finished() {
+            commands.entity(entity).despawn();
+        } else {
+            let fade = (toast.timer.remaining_secs() / 0.5).min(1.0);
+            background.0.set_alpha(0.85 * fade);
+        }
+    }
+}
+
+pub fn open_achievements_menu(mut commands: Commands, achievements: Res<Achievements>) {
+    let unlocked = achievements.defs.iter().filter(|def| achievements.is_unlocked(def)).count();
+
+    crate::overlay::spawn_overlay(
+        &mut commands,
+        AchievementsMenuUI,
+        format!("ACHIEVEMENTS  {} / {}", unlocked, achievements.defs.len()),
+        "[ESC] Back",
+        |parent| {
+            for def in achievements.defs.iter() {
+                let (mark, color) = if achievements.is_unlocked(def) {
+                    ("★", Color::srgb(1.0, 0.9, 0.3))
+                } else {
+                    ("☆", Color::srgb(0.6, 0.6, 0.6))
+                };
+                parent.spawn((
+                    Text::new(format!(
+                        "{} {} - {}  ({}/{})",
+                        mark,
+                        def.name,
+                        def.description,
+                        achievements.progress(def),
+                        def.target
+                    )),
+                    TextFont { font_size: 18.0, ..default() },
+                    TextColor(color),
+                ));
+            }
+        },
+    );
+}
@@ -0,0 +1,334 @@
+use bevy::prelude::*;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use crate::components::*;
+use crate::events::{DamageSource, PlayerDamaged};
+
+/// Share of bullets the player must dodge before the AI starts aiming.
+const GOOD_DODGE_RATE: f32 = 0.8;
+/// Enemy health fraction below which it switches to its densest patterns.
+const DESPERATE_HEALTH: f32 = 0.35;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BulletPattern {
+    Wave,
+    Spiral,
+    Spread,
+    Cross,
+    AimedShot,
+    AimedBurst,
+}
+
+impl BulletPattern {
+    pub fn aimed(&self) -> bool {
+        matches!(self, BulletPattern::AimedShot | BulletPattern::AimedBurst)
+    }
+
+    /// Patterns that come from several directions at once, which a single shield can't cover.
+    pub fn surrounds(&self) -> bool {
+        matches!(self, BulletPattern::Spiral | BulletPattern::Cross)
+    }
+
+    /// Patterns that sweep the arena edges and punish a soul hugging the walls.
+    pub fn covers_edges(&self) -> bool {
+        matches!(self, BulletPattern::Wave | BulletPattern::Spread)
+    }
+}
+
+/// What the enemy knows about the fight when it picks its next pattern.
+#[derive(Debug, Clone, Copy)]
+pub struct BattleObservation {
+    /// Fraction of last round's bullets the player avoided, 0.0 to 1.0.
+    pub dodge_rate: f32,
+    pub health_fraction: f32,
+    pub player_defended: bool,
+    /// Soul position relative to the arena centre, scaled to -1.0..1.0 on both axes.
+    pub soul_offset: Vec2,
+}
+
+/// Weights every available pattern against the observation and draws one.
+/// Pure apart from the RNG, so a seeded generator gives repeatable choices.
+pub fn choose_pattern(patterns: &[BulletPattern], obs: &BattleObservation, rng: &mut impl Rng) -> BulletPattern {
+    let weights: Vec<f32> = patterns.iter().map(|pattern| pattern_weight(*pattern, obs)).collect();
+    let total: f32 = weights.iter().sum();
+    if total <= 0.0 {
+        return patterns.first().copied().unwrap_or(BulletPattern::Wave);
+    }
+
+    let mut roll = rng.random_range(0.0..total);
+    for (pattern, weight) in patterns.iter().zip(weights) {
+        if roll < weight {
+            return *pattern;
+        }
+        roll -= weight;
+    }
+    *patterns.last().unwrap_or(&BulletPattern::Wave)
+}
+
+fn pattern_weight(pattern: BulletPattern, obs: &BattleObservation) -> f32 {
+    let mut weight = 1.0;
+
+    if obs.dodge_rate >= GOOD_DODGE_RATE && pattern.aimed() {
+        weight *= 2.5;
+    }
+    if obs.health_fraction <= DESPERATE_HEALTH
+        && matches!(pattern, BulletPattern::Spiral | BulletPattern::AimedBurst)
+    {
+        weight *= 2.0;
+    }
+    if obs.player_defended && pattern.surrounds() {
+        weight *= 2.0;
+    }
+    if obs.soul_offset.abs().max_element() > 0.6 && pattern.covers_edges() {
+        weight *= 1.5;
+    }
+
+    weight
+}
+
+/// Per-battle AI state: the seeded RNG, the pattern chosen for the current
+/// bullet phase, and how well the player dodged the previous one.
+#[derive(Resource)]
+pub struct EnemyAi {
+    pub seed: u64,
+    pub rng: StdRng,
+    pub current: Option<BulletPattern>,
+    pub dodge_rate: f32,
+    /// Totals for the whole fight; the results and score read these too.
+    pub bullets_fired: u32,
+    pub bullets_hit: u32,
+    /// Totals as the current bullet phase began.
+    phase_start: (u32, u32),
+}
+
+impl EnemyAi {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+            current: None,
+            dodge_rate: 0.5,
+            bullets_fired: 0,
+            bullets_hit: 0,
+            phase_start: (0, 0),
+        }
+    }
+
+    pub fn bullets_dodged(&self) -> u32 {
+        self.bullets_fired.saturating_sub(self.bullets_hit)
+    }
+}
+
+/// Reseeds the AI for each fight so a given run seed always plays the same battles.
+pub fn reset_enemy_ai(mut ai: ResMut<EnemyAi>, game_progress: Res<GameProgress>) {
+    ai.rng = StdRng::seed_from_u64(ai.seed.wrapping_add(game_progress.current_room as u64));
+    ai.current = None;
+    ai.dodge_rate = 0.5;
+    ai.bullets_fired = 0;
+    ai.bullets_hit = 0;
+    ai.phase_start = (0, 0);
+}
+
+/// Picks a pattern as each bullet phase starts and scores the dodging once it ends.
+/// Only bullet hits count as failed dodges; contact, status and other damage says
+/// nothing about how well the player read the pattern.
+pub fn plan_enemy_attack(
+    battle_state: Res<CurrentBattle>,
+    mut ai: ResMut<EnemyAi>,
+    enemy_query: Query<&Enemy>,
+    soul_query: Query<&Transform, With<PlayerSprite>>,
+    new_bullets: Query<(), Added<Bullet>>,
+    mut player_damaged: MessageReader<PlayerDamaged>,
+) {
+    // A hit can be read a frame after the phase that fired it has ended, so it is
+    // tallied before the phase check rather than dropped
+    ai.bullets_hit += player_damaged.read().filter(|hit| hit.source == DamageSource::Bullet).count() as u32;
+
+    if battle_state.phase != BattlePhase::BulletHell {
+        let fired = ai.bullets_fired - ai.phase_start.0;
+        let hit = ai.bullets_hit - ai.phase_start.1;
+        if ai.current.take().is_some() && fired > 0 {
+            ai.dodge_rate = 1.0 - (hit as f32 / fired as f32).min(1.0);
+        }
+        ai.phase_start = (ai.bullets_fired, ai.bullets_hit);
+        return;
+    }
+
+    ai.bullets_fired += new_bullets.iter().count() as u32;
+
+    if ai.current.is_some() {
+        return;
+    }
+    let Ok(enemy) = enemy_query.get(battle_state.enemy_entity) else { return };
+
+    let soul_offset = soul_query
+        .single()
+        .map(|t| Vec2::new(t.translation.x / (ARENA_WIDTH / 2.0), (t.translation.y - BATTLE_ARENA_Y) / (ARENA_HEIGHT / 2.0)))
+        .unwrap_or(Vec2::ZERO);
+    let obs = BattleObservation {
+        dodge_rate: ai.dodge_rate,
+        health_fraction: enemy.health.max(0) as f32 / enemy.max_health as f32,
+        player_defended: battle_state.player_defended,
+        soul_offset,
+    };
+
+    let pattern = choose_pattern(enemy.patterns, &obs, &mut ai.rng);
+    ai.current = Some(pattern);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enemies::ENEMY_ROSTER;
+
+    const ALL: [BulletPattern; 6] = [
+        BulletPattern::Wave,
+        BulletPattern::Spiral,
+        BulletPattern::Spread,
+        BulletPattern::Cross,
+        BulletPattern::AimedShot,
+        BulletPattern::AimedBurst,
+    ];
+
+    fn calm() -> BattleObservation {
+        BattleObservation {
+            dodge_rate: 0.5,
+            health_fraction: 1.0,
+            player_defended: false,
+            soul_offset: Vec2::ZERO,
+        }
+    }
+
+    fn picks(obs: &BattleObservation, seed: u64, draws: usize) -> Vec<BulletPattern> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        (0..draws).map(|_| choose_pattern(&ALL, obs, &mut rng)).collect()
+    }
+
+    fn share(picks: &[BulletPattern], wanted: impl Fn(&BulletPattern) -> bool) -> f32 {
+        picks.iter().filter(|p| wanted(p)).count() as f32 / picks.len() as f32
+    }
+
+    fn ai_app(seed: u64) -> App {
+        let mut app = App::new();
+        app.insert_resource(EnemyAi::new(seed))
+            .add_message::<PlayerDamaged>()
+            .add_systems(Update, plan_enemy_attack);
+
+        let def = &ENEMY_ROSTER[0];
+        let enemy = app
+            .world_mut()
+            .spawn(Enemy {
+                name: def.name,
+                health: def.health,
+                max_health: def.health,
+                room_index: 0,
+                attack_pattern: 0,
+                boss: false,
+                elements: def.elements,
+                bullet_element: def.bullet_element,
+                patterns: def.patterns,
+                movement: def.movement,
+                contact_damage: def.contact_damage,
+            })
+            .id();
+        app.insert_resource(CurrentBattle {
+            enemy_entity: enemy,
+            phase: BattlePhase::BulletHell,
+            phase_timer: Timer::from_seconds(1.0, TimerMode::Once),
+            player_defended: false,
+            combo_count: 0,
+            strikes_this_turn: 0,
+            graze_meter: 0.0,
+            graze_count: 0,
+            initiative: Initiative::Player,
+            rounds_survived: 0,
+        });
+        app
+    }
+
+    fn hurt(app: &mut App, source: DamageSource) {
+        app.world_mut().write_message(PlayerDamaged {
+            amount: 1,
+            affinity: Affinity::Normal,
+            source,
+            position: Vec3::ZERO,
+        });
+    }
+
+    fn end_phase(app: &mut App) {
+        app.world_mut().resource_mut::<CurrentBattle>().phase = BattlePhase::PlayerTurn;
+    }
+
+    #[test]
+    fn only_bullet_hits_count_against_dodging() {
+        let mut app = ai_app(11);
+        app.update();
+        app.world_mut().resource_mut::<EnemyAi>().bullets_fired = 4;
+
+        hurt(&mut app, DamageSource::Contact);
+        hurt(&mut app, DamageSource::Status);
+        hurt(&mut app, DamageSource::Bullet);
+        app.update();
+        assert_eq!(app.world().resource::<EnemyAi>().bullets_hit, 1);
+
+        end_phase(&mut app);
+        app.update();
+        assert_eq!(app.world().resource::<EnemyAi>().dodge_rate, 0.75);
+    }
+
+    #[test]
+    fn bullet_hit_read_after_the_phase_ends_still_counts() {
+        let mut app = ai_app(11);
+        app.update();
+        app.world_mut().resource_mut::<EnemyAi>().bullets_fired = 4;
+
+        // The hit lands on the frame the phase flips, so the AI first sees it outside the bullet phase
+        hurt(&mut app, DamageSource::Bullet);
+        end_phase(&mut app);
+        app.update();
+
+        let ai = app.world().resource::<EnemyAi>();
+        assert_eq!(ai.bullets_hit, 1);
+        assert_eq!(ai.dodge_rate, 0.75);
+    }
+
+    #[test]
+    fn same_seed_repeats_the_same_picks() {
+        assert_eq!(picks(&calm(), 42, 50), picks(&calm(), 42, 50));
+        assert_ne!(picks(&calm(), 42, 50), picks(&calm(), 43, 50));
+    }
+
+    #[test]
+    fn only_offers_listed_patterns() {
+        let patterns = [BulletPattern::Wave, BulletPattern::Cross];
+        let mut rng = StdRng::seed_from_u64(7);
+        for _ in 0..100 {
+            assert!(patterns.contains(&choose_pattern(&patterns, &calm(), &mut rng)));
+        }
+    }
+
+    #[test]
+    fn poor_dodging_does_not_favour_aimed_patterns() {
+        let obs = BattleObservation { dodge_rate: 0.2, ..calm() };
+        assert_eq!(pattern_weight(BulletPattern::AimedShot, &obs), pattern_weight(BulletPattern::Wave, &obs));
+    }
+
+    #[test]
+    fn good_dodging_favours_aimed_patterns() {
+        let obs = BattleObservation { dodge_rate: 0.9, ..calm() };
+        assert!(pattern_weight(BulletPattern::AimedShot, &obs) > pattern_weight(BulletPattern::Wave, &obs));
+
+        let aimed = |p: &BulletPattern| p.aimed();
+        assert!(share(&picks(&obs, 1, 2000), aimed) > share(&picks(&calm(), 1, 2000), aimed));
+    }
+
+    #[test]
+    fn low_health_favours_dense_patterns() {
+        let obs = BattleObservation { health_fraction: 0.2, ..calm() };
+        assert!(pattern_weight(BulletPattern::Spiral, &obs) > pattern_weight(BulletPattern::Wave, &obs));
+        assert_eq!(pattern_weight(BulletPattern::Spiral, &calm()), pattern_weight(BulletPattern::Wave, &calm()));
+
+        let dense = |p: &BulletPattern| matches!(p, BulletPattern::Spiral | BulletPattern::AimedBurst);
+        assert!(share(&picks(&obs, 2, 2000), dense) > share(&picks(&calm(), 2, 2000), dense));
+    }
+}
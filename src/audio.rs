@@ -0,0 +1,181 @@
+use std::collections::HashMap;
+
+use bevy::audio::Volume;
+use bevy::prelude::*;
+use serde::Deserialize;
+use crate::components::*;
+use crate::events::*;
+use crate::settings::Settings;
+
+const AUDIO_CONFIG_PATH: &str = "audio.ron";
+const CROSSFADE_SECS: f32 = 1.2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+pub enum MusicCue {
+    Menu,
+    Overworld,
+    Battle,
+    Boss,
+    Victory,
+    GameOver,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+pub enum Sfx {
+    Perfect,
+    Good,
+    Hit,
+    PlayerHurt,
+    Telegraph,
+    RoomClear,
+    DoorOpen,
+    Checkpoint,
+}
+
+#[derive(Message)]
+pub struct PlaySfx(pub Sfx);
+
+/// Asset paths for every music cue and sound effect, loaded from
+/// `assets/audio.ron` so sounds can be swapped without a rebuild.
+#[derive(Resource, Default, Deserialize)]
+#[serde(default)]
+pub struct AudioConfig {
+    pub music: HashMap<MusicCue, String>,
+    pub sfx: HashMap<Sfx, String>,
+}
+
+impl AudioConfig {
+    pub fn load() -> Self {
+        crate::persist::load_asset_ron(AUDIO_CONFIG_PATH)
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct MusicState {
+    pub current: Option<MusicCue>,
+}
+
+#[derive(Component)]
+pub struct MusicTrack {
+    pub level: f32,
+    pub fading_out: bool,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn select_music(
+    mut commands: Commands,
+    game_state: Res<State<GameState>>,
+    battle_state: Res<CurrentBattle>,
+    enemy_query: Query<&Enemy>,
+    config: Res<AudioConfig>,
+    asset_server: Res<AssetServer>,
+    mut music: ResMut<MusicState>,
+    mut tracks: Query<&mut MusicTrack>,
+) {
+    let cue = match game_state.get() {
+        GameState::MainMenu => MusicCue::Menu,
+        GameState::Overworld => MusicCue::Overworld,
+        GameState::Battle => {
+            let boss = enemy_query.get(battle_state.enemy_entity).is_ok_and(|enemy| enemy.boss);
+            if boss { MusicCue::Boss } else { MusicCue::Battle }
+        }
+        GameState::GameOver => MusicCue::GameOver,
+        GameState::Victory => MusicCue::Victory,
+    };
+
+    if music.current == Some(cue) {
+        return;
+    }
+    music.current = Some(cue);
+
+    for mut track in tracks.iter_mut() {
+        track.fading_out = true;
+    }
+
+    if let Some(path) = config.music.get(&cue) {
+        commands.spawn((
+            AudioPlayer::new(asset_server.load(path.clone())),
+            PlaybackSettings::LOOP.with_volume(Volume::Linear(0.0)),
+            MusicTrack { level: 0.0, fading_out: false },
+        ));
+    }
+}
+
+pub fn crossfade_music(
+    mut commands: Commands,
+    time: Res<Time<Real>>,
+    settings: Res<Settings>,
+    mut tracks: Query<(Entity, &mut MusicTrack, Option<&mut AudioSink>)>,
+) {
+    let step = time.delta_secs() / CROSSFADE_SECS;
+    let target = settings.master_volume * settings.music_volume;
+
+    for (entity, mut track, sink) in tracks.iter_mut() {
+        if track.fading_out {
+            track.level -= step;
+            if track.level <= 0.0 {
+                commands.entity(entity).despawn();
+                continue;
+            }
+        } else {
+            track.level = (track.level + step).min(1.0);
+        }
+
+        if let Some(mut sink) = sink {
+            sink.set_volume(Volume::Linear(track.level * target));
+        }
+    }
+}
+
+pub fn play_sfx(
+    mut commands: Commands,
+    mut messages: MessageReader<PlaySfx>,
+    config: Res<AudioConfig>,
+    settings: Res<Settings>,
+    asset_server: Res<AssetServer>,
+) {
+    let volume = settings.master_volume * settings.sfx_volume;
+    for PlaySfx(sfx) in messages.read() {
+        if let Some(path) = config.sfx.get(sfx) {
+            commands.spawn((
+                AudioPlayer::new(asset_server.load(path.clone())),
+                PlaybackSettings::DESPAWN.with_volume(Volume::Linear(volume)),
+            ));
+        }
+    }
+}
+
+pub fn telegraph_warning(query: Query<(), Added<Telegraph>>, mut sfx: MessageWriter<PlaySfx>) {
+    if !query.is_empty() {
+        sfx.write(PlaySfx(Sfx::Telegraph));
+    }
+}
+
+/// Maps gameplay messages onto the sound effects they should trigger.
+pub fn gameplay_sfx(
+    mut rated: MessageReader<AttackRated>,
+    mut player_damaged: MessageReader<PlayerDamaged>,
+    mut room_cleared: MessageReader<RoomCleared>,
+    mut checkpoints: MessageReader<CheckpointReached>,
+    mut sfx: MessageWriter<PlaySfx>,
+) {
+    for rated in rated.read() {
+        sfx.write(PlaySfx(match rated.rating {
+            Rating::Perfect => Sfx::Perfect,
+            Rating::Good => Sfx::Good,
+            Rating::Hit => Sfx::Hit,
+        }));
+    }
+    if player_damaged.read().count() > 0 {
+        sfx.write(PlaySfx(Sfx::PlayerHurt));
+    }
+    for cleared in room_cleared.read() {
+        sfx.write(PlaySfx(Sfx::RoomClear));
+        if cleared.all_cleared {
+            sfx.write(PlaySfx(Sfx::DoorOpen));
+        }
+    }
+    if checkpoints.read().count() > 0 {
+        sfx.write(PlaySfx(Sfx::Checkpoint));
+    }
+}
@@ -1,7 +1,18 @@
 use bevy::prelude::*;
+use crate::ai::{BulletPattern, EnemyAi};
 use crate::components::*;
+use crate::difficulty::Difficulty;
+use crate::events::*;
+use crate::minigames::{self, ActiveMinigame, MinigameSprites};
+use crate::settings::Settings;
 use rand::Rng;
 
+type OverworldPlayerTransform<'w, 's> =
+    Query<'w, 's, &'static mut Transform, (With<Player>, Without<PlayerSprite>, Without<EnemySprite>)>;
+/// Bullets and shields, which are cleared together when a round ends.
+type RoundSprites<'w, 's> =
+    Query<'w, 's, (Entity, Option<&'static Bullet>, &'static Transform), Or<(With<Bullet>, With<Shield>)>>;
+
 pub fn freeze_camera(mut camera_query: Query<&mut Transform, With<OverworldCamera>>) {
     if let Ok(mut transform) = camera_query.single_mut() {
         transform.translation = Vec3::new(0.0, BATTLE_ARENA_Y, transform.translation.z);
@@ -13,10 +24,12 @@ pub fn unfreeze_camera() {}
 pub fn setup_battle(
     mut commands: Commands,
     battle_state: Res<CurrentBattle>,
-    enemy_query: Query<&Enemy>,
+    mut enemy_query: Query<(&Enemy, &mut StatusEffects)>,
     mut spawner: ResMut<BulletSpawner>,
+    difficulty: Res<Difficulty>,
+    settings: Res<Settings>,
 ) {
-    spawner.timer = Timer::from_seconds(0.5, TimerMode::Repeating);
+    spawner.timer = Timer::from_seconds(0.5 * difficulty.spawn_interval, TimerMode::Repeating);
     
     // Arena border
     commands.spawn((
@@ -52,68 +65,44 @@ pub fn setup_battle(
             health: PLAYER_MAX_HEALTH,
             max_health: PLAYER_MAX_HEALTH,
         },
+        StatusEffects::default(),
         BattleSprite,
         PlayerSprite,
     ));
 
     // Enemy sprite
-    if let Ok(enemy) = enemy_query.get(battle_state.enemy_entity) {
+    if let Ok((enemy, mut status)) = enemy_query.get_mut(battle_state.enemy_entity) {
+        status.effects.clear();
+        if enemy.boss {
+            status.apply(StatusKind::Shield, 6, StatusDuration::Turns(3));
+        }
+
         let size = 50.0 + (enemy.attack_pattern as f32 * 7.0);
-        commands.spawn((
+        let home = Vec3::new(0.0, BATTLE_ARENA_Y + 80.0, 11.0);
+        let mut sprite = commands.spawn((
             Sprite {
-                color: Color::srgb(0.9, 0.45, 0.35),
+                color: settings.palette.enemy_color(enemy.room_index),
                 custom_size: Some(Vec2::new(size, size)),
                 ..default()
             },
-            Transform::from_translation(Vec3::new(0.0, BATTLE_ARENA_Y + 80.0, 11.0)),
+            Transform::from_translation(home),
+            ArenaMotion { path: enemy.movement, home, elapsed: 0.0 },
             BattleSprite,
             EnemySprite,
         ));
+        if enemy.contact_damage > 0 {
+            sprite.insert(ContactHazard { damage: enemy.contact_damage, cooldown: 0.0 });
+        }
     }
-
-    // Timing bar background
-    commands.spawn((
-        Sprite {
-            color: Color::srgb(0.25, 0.25, 0.25),
-            custom_size: Some(Vec2::new(320.0, 24.0)),
-            ..default()
-        },
-        Transform::from_translation(Vec3::new(0.0, BATTLE_ARENA_Y - 110.0, 11.0)),
-        BattleSprite,
-    ));
-
-    // Perfect zone
-    commands.spawn((
-        Sprite {
-            color: Color::srgba(0.3, 1.0, 0.3, 0.4),
-            custom_size: Some(Vec2::new(50.0, 24.0)),
-            ..default()
-        },
-        Transform::from_translation(Vec3::new(0.0, BATTLE_ARENA_Y - 110.0, 11.1)),
-        BattleSprite,
-    ));
-
-    // Attack indicator
-    commands.spawn((
-        Sprite {
-            color: Color::srgb(1.0, 1.0, 0.3),
-            custom_size: Some(Vec2::new(10.0, 32.0)),
-            ..default()
-        },
-        Transform::from_translation(Vec3::new(-160.0, BATTLE_ARENA_Y - 110.0, 11.5)),
-        AttackIndicator {
-            speed: 220.0,
-            direction: 1.0,
-        },
-        BattleSprite,
-    ));
 }
 
 pub fn cleanup_battle(
     mut commands: Commands,
     battle_sprites: Query<Entity, With<BattleSprite>>,
     mut battle_ui: Query<&mut Visibility, With<BattleUI>>,
+    mut minigame: ResMut<ActiveMinigame>,
 ) {
+    minigame.game = None;
     for mut visibility in battle_ui.iter_mut() {
         *visibility = Visibility::Hidden;
     }
@@ -122,142 +111,372 @@ pub fn cleanup_battle(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn battle_phase_system(
     time: Res<Time>,
     mut battle_state: ResMut<CurrentBattle>,
-    mut game_state: ResMut<NextState<GameState>>,
-    player_query: Query<&Player, With<PlayerSprite>>,
-    enemy_query: Query<&Enemy>,
+    mut player_query: Query<(&mut Player, &mut StatusEffects), With<PlayerSprite>>,
+    mut enemy_query: Query<(&mut Enemy, &mut StatusEffects), Without<PlayerSprite>>,
     mut rooms_query: Query<&mut Room>,
     mut game_progress: ResMut<GameProgress>,
     mut commands: Commands,
-    bullets: Query<Entity, With<Bullet>>,
+    round_sprites: RoundSprites,
+    enemy_sprite_query: Query<&Transform, With<EnemySprite>>,
+    mut grazed: MessageWriter<BulletGrazed>,
+    mut enemy_damaged: MessageWriter<EnemyDamaged>,
+    mut player_damaged: MessageWriter<PlayerDamaged>,
+    mut battle_ended: MessageWriter<BattleEnded>,
+    mut room_cleared: MessageWriter<RoomCleared>,
+    mut notices: MessageWriter<BattleNotice>,
+    difficulty: Res<Difficulty>,
 ) {
     battle_state.phase_timer.tick(time.delta());
+    let mut round_over = false;
 
     match battle_state.phase {
         BattlePhase::Intro => {
             if battle_state.phase_timer.just_finished() {
-                battle_state.phase = BattlePhase::PlayerTurn;
+                if battle_state.initiative == Initiative::Ambushed {
+                    battle_state.initiative = Initiative::Player;
+                    start_enemy_turn(&mut battle_state, &mut commands, &enemy_sprite_query, &mut notices);
+                } else {
+                    battle_state.phase = BattlePhase::PlayerTurn;
+                }
             }
         }
         BattlePhase::PlayerTurn => {}
         BattlePhase::EnemyTelegraph => {
             if battle_state.phase_timer.just_finished() {
-                battle_state.phase = BattlePhase::BulletHell;
-                battle_state.phase_timer = Timer::from_seconds(4.0, TimerMode::Once);
+                let stunned = enemy_query
+                    .get(battle_state.enemy_entity)
+                    .is_ok_and(|(_, status)| status.has(StatusKind::Stun));
+
+                if stunned {
+                    notices.write(BattleNotice::EnemyStunned);
+                    round_over = true;
+                } else {
+                    battle_state.phase = BattlePhase::BulletHell;
+                    battle_state.phase_timer = Timer::from_seconds(4.0 * difficulty.dodge_duration, TimerMode::Once);
+                }
             }
         }
         BattlePhase::BulletHell => {
             if battle_state.phase_timer.just_finished() {
-                battle_state.phase = BattlePhase::Resolution;
-                battle_state.phase_timer = Timer::from_seconds(1.0, TimerMode::Once);
-                
-                for bullet in bullets.iter() {
-                    commands.entity(bullet).despawn();
-                }
+                round_over = true;
             }
         }
         BattlePhase::Resolution => {
             if battle_state.phase_timer.just_finished() {
-                if let Ok(player) = player_query.single() {
+                if let Ok((player, _)) = player_query.single() {
                     if player.health <= 0 {
-                        game_state.set(GameState::GameOver);
+                        battle_ended.write(BattleEnded {
+                            enemy: battle_state.enemy_entity,
+                            outcome: BattleOutcome::Lost,
+                        });
                         return;
                     }
                 }
 
-                if let Ok(enemy) = enemy_query.get(battle_state.enemy_entity) {
+                if let Ok((enemy, _)) = enemy_query.get(battle_state.enemy_entity) {
                     if enemy.health <= 0 {
-                        for mut room in rooms_query.iter_mut() {
-                            if room.index == game_progress.current_room {
-                                room.cleared = true;
-                            }
-                        }
-                        game_progress.rooms_cleared += 1;
-                        game_state.set(GameState::Overworld);
+                        clear_current_room(&mut rooms_query, &mut game_progress, &mut room_cleared);
+                        battle_ended.write(BattleEnded {
+                            enemy: battle_state.enemy_entity,
+                            outcome: BattleOutcome::Won,
+                        });
                         return;
                     }
                 }
 
                 battle_state.phase = BattlePhase::PlayerTurn;
-                battle_state.combo_count = 0;
                 battle_state.player_defended = false;
             }
         }
     }
+
+    if round_over {
+        battle_state.rounds_survived += 1;
+        battle_state.phase = BattlePhase::Resolution;
+        battle_state.phase_timer = Timer::from_seconds(1.0, TimerMode::Once);
+
+        // Bullets still inside the graze radius when the round ends never hit
+        for (entity, bullet, transform) in round_sprites.iter() {
+            if bullet.is_some_and(|bullet| bullet.grazing) {
+                pay_graze(&mut battle_state, &mut grazed, transform.translation);
+            }
+            commands.entity(entity).despawn();
+        }
+
+        // Turn-based status effects tick once per round, on both sides
+        if let Ok((mut player, mut status)) = player_query.single_mut() {
+            let damage = status.tick_turn();
+            if damage > 0 {
+                player.health -= damage;
+                player_damaged.write(PlayerDamaged {
+                    amount: damage,
+                    affinity: Affinity::Normal,
+                    source: DamageSource::Status,
+                    position: Vec3::new(0.0, BATTLE_ARENA_Y - 50.0, 15.0),
+                });
+            }
+        }
+        if let Ok((mut enemy, mut status)) = enemy_query.get_mut(battle_state.enemy_entity) {
+            let damage = status.tick_turn();
+            if damage > 0 {
+                enemy.health -= damage;
+                enemy_damaged.write(EnemyDamaged {
+                    amount: damage,
+                    affinity: Affinity::Normal,
+                    source: DamageSource::Status,
+                    position: Vec3::new(0.0, BATTLE_ARENA_Y + 80.0, 15.0),
+                    rating: None,
+                    combo: 0,
+                    powered: false,
+                });
+            }
+        }
+    }
 }
 
-pub fn update_attack_indicator(
+pub fn tick_status_timers(time: Res<Time>, mut query: Query<&mut StatusEffects>) {
+    for mut status in query.iter_mut() {
+        status.tick_seconds(time.delta_secs());
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn player_turn_input(
+    keyboard: Res<ButtonInput<KeyCode>>,
     time: Res<Time>,
-    mut query: Query<(&mut Transform, &mut AttackIndicator)>,
-    battle_state: Res<CurrentBattle>,
+    mut battle_state: ResMut<CurrentBattle>,
+    mut commands: Commands,
+    mut minigame: ResMut<ActiveMinigame>,
+    equipment: Res<Equipment>,
+    mut minigame_sprites: MinigameSprites,
+    minigame_entities: Query<Entity, With<MinigameSprite>>,
+    enemy_query: Query<&Transform, With<EnemySprite>>,
+    mut enemy_data: Query<(&mut Enemy, &mut StatusEffects), Without<PlayerSprite>>,
+    mut soul_status: Query<&mut StatusEffects, With<PlayerSprite>>,
+    mut attack_rated: MessageWriter<AttackRated>,
+    mut enemy_damaged: MessageWriter<EnemyDamaged>,
+    mut notices: MessageWriter<BattleNotice>,
+    difficulty: Res<Difficulty>,
 ) {
     if battle_state.phase != BattlePhase::PlayerTurn {
         return;
     }
 
-    for (mut transform, mut indicator) in query.iter_mut() {
-        transform.translation.x += indicator.speed * indicator.direction * time.delta_secs();
+    if let Ok(mut status) = soul_status.single_mut() {
+        if status.has(StatusKind::Stun) {
+            status.remove(StatusKind::Stun);
+            notices.write(BattleNotice::PlayerStunned);
+            start_enemy_turn(&mut battle_state, &mut commands, &enemy_query, &mut notices);
+            return;
+        }
+    }
+
+    if keyboard.just_pressed(KeyCode::Digit2) && battle_state.strikes_this_turn == 0 {
+        minigame.game = None;
+        despawn_minigame(&mut commands, &minigame_entities);
+        battle_state.player_defended = true;
+        notices.write(BattleNotice::Defending);
+        commands.spawn((
+            Sprite {
+                color: Color::srgb(0.3, 0.8, 1.0),
+                custom_size: Some(Vec2::new(6.0, 34.0)),
+                ..default()
+            },
+            Transform::from_translation(Vec3::new(0.0, BATTLE_ARENA_Y, 11.2)),
+            Shield {
+                angle: std::f32::consts::FRAC_PI_2,
+                parry_time_left: 0.0,
+            },
+            BattleSprite,
+        ));
+        start_enemy_turn(&mut battle_state, &mut commands, &enemy_query, &mut notices);
+        return;
+    }
+
+    let Some(game) = minigame.game.as_mut() else {
+        let mut game = minigames::minigame_for(equipment.weapon, battle_state.strikes_this_turn);
+        game.spawn(&mut commands);
+        minigame.game = Some(game);
+        return;
+    };
+
+    let Some(accuracy) = game.update(&time, &keyboard, &mut minigame_sprites) else { return };
+    let accuracy = difficulty.adjust_accuracy(accuracy);
+    minigame.game = None;
+    despawn_minigame(&mut commands, &minigame_entities);
 
-        if transform.translation.x > 160.0 {
-            transform.translation.x = 160.0;
-            indicator.direction = -1.0;
-        } else if transform.translation.x < -160.0 {
-            transform.translation.x = -160.0;
-            indicator.direction = 1.0;
+    // PERFECT and GOOD hits build the combo, anything weaker breaks it
+    let combo_hit = accuracy >= GOOD_ACCURACY;
+    if combo_hit {
+        battle_state.combo_count += 1;
+    } else {
+        battle_state.combo_count = 0;
+    }
+    battle_state.strikes_this_turn += 1;
+
+    let base_damage = 5.0 + accuracy.clamp(0.0, 1.0) * 10.0;
+    let multiplier = 1.0 + battle_state.combo_count.saturating_sub(1) as f32 * COMBO_DAMAGE_STEP;
+    let mut damage = (base_damage * multiplier).round() as i32;
+
+    // A full graze meter doubles the next attack
+    let powered = battle_state.graze_meter >= GRAZE_METER_MAX;
+    if powered {
+        damage *= 2;
+        battle_state.graze_meter = 0.0;
+    }
+
+    let rating = Rating::from_accuracy(accuracy);
+    attack_rated.write(AttackRated {
+        rating,
+        combo: battle_state.combo_count,
+    });
+
+    let mut enemy_alive = false;
+    if let Ok((mut enemy, mut status)) = enemy_data.get_mut(battle_state.enemy_entity) {
+        let affinity = enemy.elements.affinity(equipment.weapon.element());
+        damage = status.absorb(affinity.apply(damage));
+        enemy.health -= damage;
+        enemy_alive = enemy.health > 0;
+
+        if let Some((kind, stacks, duration)) = equipment.weapon.on_hit_status(accuracy) {
+            status.apply(kind, stacks, duration);
         }
+
+        let position = enemy_query
+            .single()
+            .map(|t| t.translation)
+            .unwrap_or(Vec3::new(0.0, BATTLE_ARENA_Y + 80.0, 15.0));
+        enemy_damaged.write(EnemyDamaged {
+            amount: damage,
+            affinity,
+            source: DamageSource::Strike,
+            position,
+            rating: Some(rating),
+            combo: battle_state.combo_count,
+            powered,
+        });
+    }
+
+    // While the combo holds the minigame runs again for another strike
+    if !(combo_hit && enemy_alive && battle_state.strikes_this_turn < MAX_STRIKES_PER_TURN) {
+        start_enemy_turn(&mut battle_state, &mut commands, &enemy_query, &mut notices);
     }
 }
 
-pub fn player_turn_input(
+#[allow(clippy::too_many_arguments)]
+pub fn flee_input(
     keyboard: Res<ButtonInput<KeyCode>>,
     mut battle_state: ResMut<CurrentBattle>,
     mut commands: Commands,
-    indicator_query: Query<&Transform, With<AttackIndicator>>,
+    mut minigame: ResMut<ActiveMinigame>,
+    minigame_entities: Query<Entity, With<MinigameSprite>>,
+    soul_query: Query<&Player, With<PlayerSprite>>,
+    enemy_data: Query<(&Enemy, &Transform), Without<Player>>,
+    mut overworld_player: OverworldPlayerTransform,
     enemy_query: Query<&Transform, With<EnemySprite>>,
-    mut enemy_data: Query<&mut Enemy>,
-    mut shake_query: Query<&mut ScreenShake>,
+    mut battle_ended: MessageWriter<BattleEnded>,
+    mut notices: MessageWriter<BattleNotice>,
 ) {
-    if battle_state.phase != BattlePhase::PlayerTurn {
+    if battle_state.phase != BattlePhase::PlayerTurn
+        || battle_state.strikes_this_turn > 0
+        || !keyboard.just_pressed(KeyCode::Digit3)
+    {
         return;
     }
+    let Ok(soul) = soul_query.single() else { return };
+    let Ok((enemy, enemy_transform)) = enemy_data.get(battle_state.enemy_entity) else { return };
 
-    if keyboard.just_pressed(KeyCode::Space) {
-        if let Ok(indicator_transform) = indicator_query.single() {
-            let indicator_x = indicator_transform.translation.x;
-            let distance = indicator_x.abs();
-            
-            let (damage, text, color) = if distance < 25.0 {
-                (15, "★ PERFECT! ★", Color::srgb(1.0, 1.0, 0.3))
-            } else if distance < 70.0 {
-                (10, "GOOD!", Color::srgb(0.3, 1.0, 0.3))
-            } else {
-                (5, "Hit", Color::srgb(0.7, 0.7, 0.7))
-            };
-
-            if let Ok(mut enemy) = enemy_data.get_mut(battle_state.enemy_entity) {
-                enemy.health -= damage;
+    if enemy.boss {
+        notices.write(BattleNotice::CantEscape);
+        return;
+    }
 
-                if let Ok(enemy_transform) = enemy_query.single() {
-                    spawn_damage(&mut commands, format!("{}\n-{}", text, damage), 
-                        Vec3::new(80.0, BATTLE_ARENA_Y + 80.0, 15.0), color);
-                    spawn_particles(&mut commands, enemy_transform.translation, color, 12);
+    minigame.game = None;
+    despawn_minigame(&mut commands, &minigame_entities);
 
-                    if let Ok(mut shake) = shake_query.single_mut() {
-                        shake.trauma = if distance < 25.0 { 0.6 } else { 0.3 };
-                    }
-                }
-            }
+    if rand::rng().random::<f32>() < enemy.flee_chance(soul) {
+        // Step back out of the encounter radius so the fight doesn't restart
+        if let Ok(mut player_transform) = overworld_player.single_mut() {
+            let away = (player_transform.translation - enemy_transform.translation)
+                .truncate()
+                .try_normalize()
+                .unwrap_or(Vec2::NEG_Y);
+            let pos = enemy_transform.translation.truncate() + away * FLEE_PUSHBACK;
+            player_transform.translation.x = pos.x.clamp(-110.0, 110.0);
+            player_transform.translation.y = pos.y;
+        }
+        battle_ended.write(BattleEnded {
+            enemy: battle_state.enemy_entity,
+            outcome: BattleOutcome::Fled,
+        });
+    } else {
+        notices.write(BattleNotice::FleeFailed);
+        start_enemy_turn(&mut battle_state, &mut commands, &enemy_query, &mut notices);
+    }
+}
 
-            start_enemy_turn(&mut battle_state, &mut commands, &enemy_query);
+/// Lets the enemy go once it can be spared, clearing its room without a finishing blow.
+#[allow(clippy::too_many_arguments)]
+pub fn spare_input(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut battle_state: ResMut<CurrentBattle>,
+    mut commands: Commands,
+    mut minigame: ResMut<ActiveMinigame>,
+    minigame_entities: Query<Entity, With<MinigameSprite>>,
+    enemy_data: Query<&Enemy>,
+    mut rooms_query: Query<&mut Room>,
+    mut game_progress: ResMut<GameProgress>,
+    mut battle_ended: MessageWriter<BattleEnded>,
+    mut room_cleared: MessageWriter<RoomCleared>,
+    mut notices: MessageWriter<BattleNotice>,
+) {
+    if battle_state.phase != BattlePhase::PlayerTurn
+        || battle_state.strikes_this_turn > 0
+        || !keyboard.just_pressed(KeyCode::Digit4)
+    {
+        return;
+    }
+    let Ok(enemy) = enemy_data.get(battle_state.enemy_entity) else { return };
+
+    if !enemy.can_spare(battle_state.rounds_survived) {
+        notices.write(BattleNotice::NotReadyToYield);
+        return;
+    }
+
+    minigame.game = None;
+    despawn_minigame(&mut commands, &minigame_entities);
+    battle_state.phase = BattlePhase::Resolution;
+    clear_current_room(&mut rooms_query, &mut game_progress, &mut room_cleared);
+    battle_ended.write(BattleEnded {
+        enemy: battle_state.enemy_entity,
+        outcome: BattleOutcome::Spared,
+    });
+}
+
+fn clear_current_room(
+    rooms_query: &mut Query<&mut Room>,
+    game_progress: &mut GameProgress,
+    room_cleared: &mut MessageWriter<RoomCleared>,
+) {
+    for mut room in rooms_query.iter_mut() {
+        if room.index == game_progress.current_room {
+            room.cleared = true;
         }
     }
+    game_progress.rooms_cleared += 1;
+    room_cleared.write(RoomCleared {
+        room: game_progress.current_room,
+        all_cleared: game_progress.rooms_cleared >= game_progress.total_rooms,
+    });
+}
 
-    if keyboard.just_pressed(KeyCode::Digit2) {
-        battle_state.player_defended = true;
-        spawn_text(&mut commands, "⚔ DEFENDING ⚔", Vec3::new(0.0, BATTLE_ARENA_Y + 10.0, 15.0), Color::srgb(0.3, 0.8, 1.0));
-        start_enemy_turn(&mut battle_state, &mut commands, &enemy_query);
+fn despawn_minigame(commands: &mut Commands, minigame_entities: &Query<Entity, With<MinigameSprite>>) {
+    for entity in minigame_entities.iter() {
+        commands.entity(entity).despawn();
     }
 }
 
@@ -265,7 +484,19 @@ fn start_enemy_turn(
     battle_state: &mut ResMut<CurrentBattle>,
     commands: &mut Commands,
     enemy_query: &Query<&Transform, With<EnemySprite>>,
+    notices: &mut MessageWriter<BattleNotice>,
 ) {
+    battle_state.strikes_this_turn = 0;
+
+    // A preemptive strike means the enemy loses its first attack
+    if battle_state.initiative == Initiative::Preemptive {
+        battle_state.initiative = Initiative::Player;
+        battle_state.phase = BattlePhase::Resolution;
+        battle_state.phase_timer = Timer::from_seconds(1.0, TimerMode::Once);
+        notices.write(BattleNotice::CaughtOffGuard);
+        return;
+    }
+
     battle_state.phase = BattlePhase::EnemyTelegraph;
     battle_state.phase_timer = Timer::from_seconds(1.5, TimerMode::Once);
 
@@ -285,13 +516,16 @@ fn start_enemy_turn(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn spawn_bullet_patterns(
     mut commands: Commands,
     time: Res<Time>,
     mut spawner: ResMut<BulletSpawner>,
     battle_state: Res<CurrentBattle>,
+    ai: Res<EnemyAi>,
     enemy_query: Query<&Enemy>,
     enemy_sprite_query: Query<&Transform, With<EnemySprite>>,
+    soul_query: Query<&Transform, With<PlayerSprite>>,
 ) {
     if battle_state.phase != BattlePhase::BulletHell {
         return;
@@ -300,53 +534,82 @@ pub fn spawn_bullet_patterns(
     spawner.timer.tick(time.delta());
 
     if spawner.timer.just_finished() {
+        let Some(pattern) = ai.current else { return };
         if let Ok(enemy) = enemy_query.get(battle_state.enemy_entity) {
             if let Ok(transform) = enemy_sprite_query.single() {
-                match enemy.attack_pattern % 4 {
-                    0 => spawn_wave(&mut commands, transform.translation),
-                    1 => spawn_spiral(&mut commands, transform.translation),
-                    2 => spawn_spread(&mut commands, transform.translation),
-                    _ => spawn_cross(&mut commands, transform.translation),
+                let element = enemy.bullet_element;
+                let origin = transform.translation;
+                let target = soul_query.single().map(|t| t.translation).unwrap_or(origin - Vec3::Y);
+                match pattern {
+                    BulletPattern::Wave => spawn_wave(&mut commands, origin, element),
+                    BulletPattern::Spiral => spawn_spiral(&mut commands, origin, element, Some(StatusKind::Slow)),
+                    BulletPattern::Spread => spawn_spread(&mut commands, origin, element),
+                    BulletPattern::Cross => spawn_cross(&mut commands, origin, element, Some(StatusKind::Burn)),
+                    BulletPattern::AimedShot => spawn_aimed_shot(&mut commands, origin, target, element),
+                    BulletPattern::AimedBurst => spawn_aimed_burst(&mut commands, origin, target, element),
                 }
             }
         }
     }
 }
 
-fn spawn_wave(commands: &mut Commands, origin: Vec3) {
+fn spawn_wave(commands: &mut Commands, origin: Vec3, element: Element) {
     for i in 0..3 {
         let offset_x = (i as f32 - 1.0) * 60.0;
-        spawn_bullet(commands, origin + Vec3::new(offset_x, 0.0, 0.0), Vec2::new(0.0, -70.0));
+        spawn_bullet(commands, origin + Vec3::new(offset_x, 0.0, 0.0), Vec2::new(0.0, -70.0), element, None);
     }
 }
 
-fn spawn_spiral(commands: &mut Commands, origin: Vec3) {
+fn spawn_spiral(commands: &mut Commands, origin: Vec3, element: Element, status: Option<StatusKind>) {
     for i in 0..6 {
         let angle = i as f32 * std::f32::consts::TAU / 6.0;
         let vel = Vec2::new(angle.cos() * 65.0, angle.sin() * 65.0);
-        spawn_bullet(commands, origin, vel);
+        spawn_bullet(commands, origin, vel, element, status);
     }
 }
 
-fn spawn_spread(commands: &mut Commands, origin: Vec3) {
+fn spawn_spread(commands: &mut Commands, origin: Vec3, element: Element) {
     for i in 0..5 {
         let angle = -0.6 + (i as f32 * 0.3);
         let vel = Vec2::new(angle.sin() * 75.0, -angle.cos() * 75.0);
-        spawn_bullet(commands, origin, vel);
+        spawn_bullet(commands, origin, vel, element, None);
     }
 }
 
-fn spawn_cross(commands: &mut Commands, origin: Vec3) {
+fn spawn_cross(commands: &mut Commands, origin: Vec3, element: Element, status: Option<StatusKind>) {
     let dirs = [Vec2::new(1.0, 0.0), Vec2::new(-1.0, 0.0), Vec2::new(0.0, 1.0), Vec2::new(0.0, -1.0)];
     for dir in dirs {
-        spawn_bullet(commands, origin, dir * 70.0);
+        spawn_bullet(commands, origin, dir * 70.0, element, status);
     }
 }
 
-fn spawn_bullet(commands: &mut Commands, position: Vec3, velocity: Vec2) {
+/// Three bullets in a narrow fan centred on the soul.
+fn spawn_aimed_shot(commands: &mut Commands, origin: Vec3, target: Vec3, element: Element) {
+    let aim = (target - origin).truncate().normalize_or(Vec2::NEG_Y);
+    for i in 0..3 {
+        let vel = Vec2::from_angle((i as f32 - 1.0) * 0.15).rotate(aim) * 80.0;
+        spawn_bullet(commands, origin, vel, element, None);
+    }
+}
+
+/// A staggered line of bullets fired straight at the soul.
+fn spawn_aimed_burst(commands: &mut Commands, origin: Vec3, target: Vec3, element: Element) {
+    let aim = (target - origin).truncate().normalize_or(Vec2::NEG_Y);
+    for i in 0..4 {
+        spawn_bullet(commands, origin, aim * (60.0 + i as f32 * 15.0), element, None);
+    }
+}
+
+fn spawn_bullet(commands: &mut Commands, position: Vec3, velocity: Vec2, element: Element, status: Option<StatusKind>) {
+    let color = match status {
+        Some(StatusKind::Slow) => Color::srgb(0.5, 0.7, 1.0),
+        Some(StatusKind::Burn) => Color::srgb(1.0, 0.5, 0.1),
+        _ => Color::srgb(1.0, 0.95, 0.2),
+    };
+
     commands.spawn((
         Sprite {
-            color: Color::srgb(1.0, 0.95, 0.2),
+            color,
             custom_size: Some(Vec2::new(28.0, 28.0)),
             ..default()
         },
@@ -355,6 +618,10 @@ fn spawn_bullet(commands: &mut Commands, position: Vec3, velocity: Vec2) {
             velocity,
             damage: 4,
             lifetime: Timer::from_seconds(8.0, TimerMode::Once),
+            grazing: false,
+            reflected: false,
+            status,
+            element,
         },
         BattleSprite,
     ));
@@ -363,49 +630,198 @@ fn spawn_bullet(commands: &mut Commands, position: Vec3, velocity: Vec2) {
 pub fn update_bullets(
     mut commands: Commands,
     time: Res<Time>,
+    settings: Res<Settings>,
+    difficulty: Res<Difficulty>,
+    mut battle_state: ResMut<CurrentBattle>,
     mut query: Query<(Entity, &mut Transform, &mut Bullet)>,
+    mut grazed: MessageWriter<BulletGrazed>,
 ) {
     for (entity, mut transform, mut bullet) in query.iter_mut() {
         bullet.lifetime.tick(time.delta());
         
-        transform.translation.x += bullet.velocity.x * time.delta_secs();
-        transform.translation.y += bullet.velocity.y * time.delta_secs();
+        let step = time.delta_secs() * settings.bullet_speed * settings.assist.bullet_speed() * difficulty.bullet_speed;
+        transform.translation.x += bullet.velocity.x * step;
+        transform.translation.y += bullet.velocity.y * step;
 
         if bullet.lifetime.is_finished() || 
            transform.translation.x.abs() > 500.0 || 
            (transform.translation.y - BATTLE_ARENA_Y).abs() > 300.0 {
+            if bullet.grazing {
+                pay_graze(&mut battle_state, &mut grazed, transform.translation);
+            }
             commands.entity(entity).despawn();
         }
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn check_bullet_collision(
     mut commands: Commands,
-    bullet_query: Query<(Entity, &Transform, &Bullet)>,
-    mut player_query: Query<(&Transform, &mut Player), With<PlayerSprite>>,
-    battle_state: Res<CurrentBattle>,
+    mut bullet_query: Query<(Entity, &Transform, &mut Bullet, &mut Sprite)>,
+    mut player_query: Query<(&Transform, &mut Player, &mut StatusEffects), With<PlayerSprite>>,
+    shield_query: Query<&Shield>,
+    enemy_sprite_query: Query<&Transform, With<EnemySprite>>,
+    mut battle_state: ResMut<CurrentBattle>,
+    equipment: Res<Equipment>,
+    difficulty: Res<Difficulty>,
+    mut player_damaged: MessageWriter<PlayerDamaged>,
+    mut deflected: MessageWriter<BulletDeflected>,
+    mut grazed: MessageWriter<BulletGrazed>,
 ) {
-    let Ok((player_transform, mut player)) = player_query.single_mut() else { return };
+    let Ok((player_transform, mut player, mut status)) = player_query.single_mut() else { return };
+    let shield = shield_query.single().ok();
 
-    for (bullet_entity, bullet_transform, bullet) in bullet_query.iter() {
-        let distance = player_transform.translation.distance(bullet_transform.translation);
+    for (bullet_entity, bullet_transform, mut bullet, mut sprite) in bullet_query.iter_mut() {
+        if bullet.reflected {
+            continue;
+        }
+
+        let to_bullet = bullet_transform.translation.truncate() - player_transform.translation.truncate();
+        let distance = to_bullet.length();
+
+        if let Some(shield) = shield {
+            let facing = Vec2::from_angle(shield.angle);
+            let blocked = distance < SHIELD_DISTANCE + 14.0
+                && facing.angle_to(to_bullet).abs() < SHIELD_HALF_ARC;
+
+            if blocked {
+                let parried = shield.parry_time_left > 0.0;
+                let target = enemy_sprite_query
+                    .single()
+                    .map(|t| t.translation.truncate())
+                    .unwrap_or(Vec2::new(0.0, BATTLE_ARENA_Y + 80.0));
+                let speed = bullet.velocity.length().max(60.0);
+
+                bullet.reflected = true;
+                bullet.grazing = false;
+                bullet.velocity = (target - bullet_transform.translation.truncate()).normalize_or_zero() * speed * 1.6;
+                sprite.color = Color::srgb(0.3, 0.8, 1.0);
+
+                if parried {
+                    // A parry doubles the bounce-back and refunds some resources
+                    bullet.damage *= 2;
+                    battle_state.graze_meter = (battle_state.graze_meter + PARRY_GRAZE_REFUND).min(GRAZE_METER_MAX);
+                    player.health = (player.health + 1).min(player.max_health);
+                    // Two turns, since this round's tick would otherwise strip it straight away
+                    status.apply(StatusKind::Shield, 2, StatusDuration::Turns(2));
+                }
+                deflected.write(BulletDeflected { parried, position: bullet_transform.translation });
+                continue;
+            }
+        }
 
         if distance < 25.0 {
-            let damage = if battle_state.player_defended { 1 } else { bullet.damage };
+            // A bullet that hits was never a graze, however close it came first
+            bullet.grazing = false;
+            let affinity = equipment.armor.elements().affinity(bullet.element);
+            let damage = status.absorb(affinity.apply(difficulty.scale_damage(bullet.damage)));
             player.health -= damage;
+            battle_state.combo_count = 0;
+
+            match bullet.status {
+                Some(StatusKind::Slow) => status.apply(StatusKind::Slow, 1, StatusDuration::Seconds(2.0)),
+                Some(kind) => status.apply(kind, 1, StatusDuration::Turns(2)),
+                None => {}
+            }
+
+            player_damaged.write(PlayerDamaged {
+                amount: damage,
+                affinity,
+                source: DamageSource::Bullet,
+                position: bullet_transform.translation,
+            });
+
+            commands.entity(bullet_entity).despawn();
+        } else if distance < GRAZE_RADIUS {
+            bullet.grazing = true;
+        } else if bullet.grazing {
+            bullet.grazing = false;
+            let midpoint = (player_transform.translation + bullet_transform.translation) / 2.0;
+            pay_graze(&mut battle_state, &mut grazed, midpoint);
+        }
+    }
+}
 
-            spawn_damage(&mut commands, format!("-{}", damage), 
-                Vec3::new(-100.0, BATTLE_ARENA_Y - 50.0, 15.0), Color::srgb(1.0, 0.6, 0.3));
-            spawn_particles(&mut commands, bullet_transform.translation, Color::srgb(1.0, 0.7, 0.3), 10);
+/// Rewards a bullet that came inside the graze radius and went on without hitting.
+fn pay_graze(battle_state: &mut CurrentBattle, grazed: &mut MessageWriter<BulletGrazed>, position: Vec3) {
+    battle_state.graze_count += 1;
+    battle_state.graze_meter = (battle_state.graze_meter + GRAZE_PER_BULLET).min(GRAZE_METER_MAX);
+    grazed.write(BulletGrazed { position });
+}
 
+pub fn check_reflected_bullets(
+    mut commands: Commands,
+    bullet_query: Query<(Entity, &Transform, &Bullet)>,
+    enemy_sprite_query: Query<(&Transform, &Sprite), With<EnemySprite>>,
+    mut enemy_data: Query<&mut Enemy>,
+    battle_state: Res<CurrentBattle>,
+    mut enemy_damaged: MessageWriter<EnemyDamaged>,
+) {
+    let Ok((enemy_transform, enemy_sprite)) = enemy_sprite_query.single() else { return };
+    let Ok(mut enemy) = enemy_data.get_mut(battle_state.enemy_entity) else { return };
+    let radius = enemy_sprite.custom_size.map(|size| size.x / 2.0).unwrap_or(25.0);
+
+    for (bullet_entity, bullet_transform, bullet) in bullet_query.iter() {
+        if !bullet.reflected {
+            continue;
+        }
+
+        if bullet_transform.translation.truncate().distance(enemy_transform.translation.truncate()) < radius {
+            let affinity = enemy.elements.affinity(bullet.element);
+            let damage = affinity.apply(bullet.damage);
+            enemy.health -= damage;
+            enemy_damaged.write(EnemyDamaged {
+                amount: damage,
+                affinity,
+                source: DamageSource::Reflect,
+                position: bullet_transform.translation,
+                rating: None,
+                combo: 0,
+                powered: false,
+            });
             commands.entity(bullet_entity).despawn();
         }
     }
 }
 
+pub fn update_shield(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    time: Res<Time>,
+    battle_state: Res<CurrentBattle>,
+    player_query: Query<&Transform, With<PlayerSprite>>,
+    mut shield_query: Query<(&mut Transform, &mut Sprite, &mut Shield), Without<PlayerSprite>>,
+) {
+    let Ok(player_transform) = player_query.single() else { return };
+    let Ok((mut transform, mut sprite, mut shield)) = shield_query.single_mut() else { return };
+
+    if battle_state.phase == BattlePhase::BulletHell {
+        if keyboard.pressed(KeyCode::KeyQ) {
+            shield.angle += SHIELD_TURN_SPEED * time.delta_secs();
+        }
+        if keyboard.pressed(KeyCode::KeyE) {
+            shield.angle -= SHIELD_TURN_SPEED * time.delta_secs();
+        }
+        if keyboard.just_pressed(KeyCode::Space) {
+            shield.parry_time_left = PARRY_WINDOW;
+        }
+    }
+    shield.parry_time_left = (shield.parry_time_left - time.delta_secs()).max(0.0);
+
+    let facing = Vec2::from_angle(shield.angle);
+    transform.translation.x = player_transform.translation.x + facing.x * SHIELD_DISTANCE;
+    transform.translation.y = player_transform.translation.y + facing.y * SHIELD_DISTANCE;
+    transform.rotation = Quat::from_rotation_z(shield.angle);
+
+    sprite.color = if shield.parry_time_left > 0.0 {
+        Color::srgb(1.0, 1.0, 1.0)
+    } else {
+        Color::srgb(0.3, 0.8, 1.0)
+    };
+}
+
 pub fn bullet_hell_player_movement(
     keyboard: Res<ButtonInput<KeyCode>>,
-    mut query: Query<&mut Transform, With<PlayerSprite>>,
+    mut query: Query<(&mut Transform, &StatusEffects), With<PlayerSprite>>,
     time: Res<Time>,
     battle_state: Res<CurrentBattle>,
 ) {
@@ -413,8 +829,8 @@ pub fn bullet_hell_player_movement(
         return;
     }
 
-    let Ok(mut transform) = query.single_mut() else { return };
-    let speed = 150.0;
+    let Ok((mut transform, status)) = query.single_mut() else { return };
+    let speed = if status.has(StatusKind::Slow) { 75.0 } else { 150.0 };
     let mut direction = Vec2::ZERO;
 
     if keyboard.pressed(KeyCode::KeyA) || keyboard.pressed(KeyCode::ArrowLeft) {
@@ -443,16 +859,79 @@ pub fn bullet_hell_player_movement(
     transform.translation.y = transform.translation.y.clamp(BATTLE_ARENA_Y - half_h, BATTLE_ARENA_Y + half_h);
 }
 
+/// Walks the enemy along its path while bullets fly, then eases it back home.
+pub fn move_enemy_sprite(
+    time: Res<Time>,
+    battle_state: Res<CurrentBattle>,
+    mut query: Query<(&mut Transform, &mut ArenaMotion), With<EnemySprite>>,
+) {
+    let Ok((mut transform, mut motion)) = query.single_mut() else { return };
+
+    if battle_state.phase == BattlePhase::BulletHell {
+        motion.elapsed += time.delta_secs();
+        let offset = motion.path.offset(motion.elapsed);
+        transform.translation = motion.home + offset.extend(0.0);
+    } else {
+        motion.elapsed = 0.0;
+        let home = motion.home;
+        transform.translation = transform.translation.lerp(home, (time.delta_secs() * 6.0).min(1.0));
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn check_enemy_contact(
+    time: Res<Time>,
+    mut battle_state: ResMut<CurrentBattle>,
+    equipment: Res<Equipment>,
+    difficulty: Res<Difficulty>,
+    enemy_data: Query<&Enemy>,
+    mut hazard_query: Query<(&Transform, &Sprite, &mut ContactHazard), With<EnemySprite>>,
+    mut player_query: Query<(&Transform, &mut Player, &mut StatusEffects), With<PlayerSprite>>,
+    mut player_damaged: MessageWriter<PlayerDamaged>,
+) {
+    let Ok((enemy_transform, enemy_sprite, mut hazard)) = hazard_query.single_mut() else { return };
+    hazard.cooldown = (hazard.cooldown - time.delta_secs()).max(0.0);
+
+    if battle_state.phase != BattlePhase::BulletHell || hazard.cooldown > 0.0 {
+        return;
+    }
+    let Ok((player_transform, mut player, mut status)) = player_query.single_mut() else { return };
+    let Ok(enemy) = enemy_data.get(battle_state.enemy_entity) else { return };
+
+    let radius = enemy_sprite.custom_size.map(|size| size.x / 2.0).unwrap_or(25.0) + 11.0;
+    if player_transform.translation.truncate().distance(enemy_transform.translation.truncate()) >= radius {
+        return;
+    }
+
+    let affinity = equipment.armor.elements().affinity(enemy.bullet_element);
+    let damage = status.absorb(affinity.apply(difficulty.scale_damage(hazard.damage)));
+    player.health -= damage;
+    battle_state.combo_count = 0;
+    hazard.cooldown = 1.0;
+
+    player_damaged.write(PlayerDamaged {
+        amount: damage,
+        affinity,
+        source: DamageSource::Contact,
+        position: player_transform.translation,
+    });
+}
+
 pub fn update_telegraph(
     time: Res<Time>,
+    settings: Res<Settings>,
     mut commands: Commands,
     mut query: Query<(Entity, &mut Sprite, &mut Telegraph)>,
 ) {
     for (entity, mut sprite, mut telegraph) in query.iter_mut() {
         telegraph.timer.tick(time.delta());
         
-        let pulse = (time.elapsed_secs() * 15.0).sin() * 0.5 + 0.5;
-        sprite.color.set_alpha(0.5 + pulse * 0.4);
+        if settings.reduce_flashing {
+            sprite.color.set_alpha(0.7);
+        } else {
+            let pulse = (time.elapsed_secs() * 15.0).sin() * 0.5 + 0.5;
+            sprite.color.set_alpha(0.5 + pulse * 0.4);
+        }
 
         if telegraph.timer.is_finished() {
             commands.entity(entity).despawn();
@@ -460,53 +939,171 @@ pub fn update_telegraph(
     }
 }
 
-fn spawn_damage(commands: &mut Commands, text: String, pos: Vec3, color: Color) {
-    commands.spawn((
-        Text::new(text),
-        TextFont { font_size: 26.0, ..default() },
-        TextColor(color),
-        Transform::from_translation(pos),
-        DamageNotif {
-            timer: Timer::from_seconds(1.0, TimerMode::Once),
-            velocity: Vec2::new(0.0, 50.0),
-        },
-        BattleSprite,
-    ));
-}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enemies::ENEMY_ROSTER;
 
-fn spawn_text(commands: &mut Commands, text: &str, pos: Vec3, color: Color) {
-    commands.spawn((
-        Text::new(text),
-        TextFont { font_size: 22.0, ..default() },
-        TextColor(color),
-        Transform::from_translation(pos),
-        DamageNotif {
-            timer: Timer::from_seconds(1.3, TimerMode::Once),
-            velocity: Vec2::new(0.0, 30.0),
-        },
-        BattleSprite,
-    ));
-}
+    fn battle_app(enemy_health: i32) -> (App, Entity) {
+        let mut app = App::new();
+        app.init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<Time>()
+            .init_resource::<ActiveMinigame>()
+            .init_resource::<Difficulty>()
+            .insert_resource(Equipment { weapon: Weapon::Sword, armor: Armor::Leather })
+            .insert_resource(GameProgress { current_room: 0, rooms_cleared: 0, total_rooms: TOTAL_ROOMS, assisted: false })
+            .add_message::<AttackRated>()
+            .add_message::<EnemyDamaged>()
+            .add_message::<BattleEnded>()
+            .add_message::<RoomCleared>()
+            .add_message::<BattleNotice>()
+            .add_message::<PlayerDamaged>()
+            .add_message::<BulletDeflected>()
+            .add_message::<BulletGrazed>()
+            .add_systems(Update, (player_turn_input, spare_input, check_bullet_collision));
 
-fn spawn_particles(commands: &mut Commands, pos: Vec3, color: Color, count: usize) {
-    let mut rng = rand::rng();
-    for _ in 0..count {
-        let angle = rng.random_range(0.0..std::f32::consts::TAU);
-        let speed = rng.random_range(60.0..120.0);
-        let vel = Vec2::new(angle.cos() * speed, angle.sin() * speed);
-        
-        commands.spawn((
-            Sprite {
-                color,
-                custom_size: Some(Vec2::new(5.0, 5.0)),
-                ..default()
-            },
-            Transform::from_translation(pos),
-            Particle {
-                timer: Timer::from_seconds(0.6, TimerMode::Once),
-                velocity: vel,
-            },
-            BattleSprite,
+        let def = &ENEMY_ROSTER[0];
+        let enemy = app
+            .world_mut()
+            .spawn((
+                Enemy {
+                    name: def.name,
+                    health: enemy_health,
+                    max_health: def.health,
+                    room_index: 0,
+                    attack_pattern: 0,
+                    boss: false,
+                    elements: def.elements,
+                    bullet_element: def.bullet_element,
+                    patterns: def.patterns,
+                    movement: def.movement,
+                    contact_damage: def.contact_damage,
+                },
+                StatusEffects::default(),
+            ))
+            .id();
+        app.world_mut().spawn(Room { index: 0, cleared: false });
+        app.insert_resource(CurrentBattle {
+            enemy_entity: enemy,
+            phase: BattlePhase::PlayerTurn,
+            phase_timer: Timer::from_seconds(1.0, TimerMode::Once),
+            player_defended: false,
+            combo_count: 0,
+            strikes_this_turn: 0,
+            graze_meter: 0.0,
+            graze_count: 0,
+            initiative: Initiative::Player,
+            rounds_survived: 0,
+        });
+        (app, enemy)
+    }
+
+    fn press(app: &mut App, key: KeyCode) {
+        app.world_mut().resource_mut::<ButtonInput<KeyCode>>().press(key);
+    }
+
+    fn written<M: Message + Copy>(app: &App) -> Vec<M> {
+        let messages = app.world().resource::<Messages<M>>();
+        messages.get_cursor().read(messages).copied().collect()
+    }
+
+    #[test]
+    fn strike_reports_rated_damage() {
+        let (mut app, enemy) = battle_app(ENEMY_ROSTER[0].health);
+        // The first frame sets up the minigame, the next one takes the strike
+        app.update();
+        press(&mut app, KeyCode::Space);
+        app.update();
+
+        let hits = written::<EnemyDamaged>(&app);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].source, DamageSource::Strike);
+        assert!(hits[0].rating.is_some());
+        let health = app.world().get::<Enemy>(enemy).unwrap().health;
+        assert_eq!(health, ENEMY_ROSTER[0].health - hits[0].amount);
+    }
+
+    #[test]
+    fn spare_ends_the_battle_once_the_enemy_yields() {
+        let (mut app, enemy) = battle_app(1);
+        press(&mut app, KeyCode::Digit4);
+        app.update();
+
+        let ended = written::<BattleEnded>(&app);
+        assert_eq!(ended.len(), 1);
+        assert_eq!(ended[0].enemy, enemy);
+        assert_eq!(ended[0].outcome, BattleOutcome::Spared);
+        assert_eq!(written::<RoomCleared>(&app).len(), 1);
+    }
+
+    #[test]
+    fn spare_is_refused_while_the_enemy_is_healthy() {
+        let (mut app, _) = battle_app(ENEMY_ROSTER[0].health);
+        press(&mut app, KeyCode::Digit4);
+        app.update();
+
+        assert!(written::<BattleEnded>(&app).is_empty());
+        assert_eq!(written::<BattleNotice>(&app), vec![BattleNotice::NotReadyToYield]);
+    }
+
+    /// A soul in the arena and one bullet `offset` away from it, mid dodge phase.
+    fn dodge_app(offset: Vec2) -> (App, Entity) {
+        let (mut app, _) = battle_app(ENEMY_ROSTER[0].health);
+        app.world_mut().resource_mut::<CurrentBattle>().phase = BattlePhase::BulletHell;
+        let soul = Vec3::new(0.0, BATTLE_ARENA_Y, 11.0);
+        app.world_mut().spawn((
+            Transform::from_translation(soul),
+            Player { health: PLAYER_MAX_HEALTH, max_health: PLAYER_MAX_HEALTH },
+            StatusEffects::default(),
+            PlayerSprite,
         ));
+        let bullet = app
+            .world_mut()
+            .spawn((
+                Transform::from_translation(soul + offset.extend(0.0)),
+                Sprite::default(),
+                Bullet {
+                    velocity: Vec2::ZERO,
+                    damage: 4,
+                    lifetime: Timer::from_seconds(8.0, TimerMode::Once),
+                    grazing: false,
+                    reflected: false,
+                    status: None,
+                    element: Element::Physical,
+                },
+            ))
+            .id();
+        (app, bullet)
+    }
+
+    fn move_bullet(app: &mut App, bullet: Entity, offset: Vec2) {
+        let soul = Vec3::new(0.0, BATTLE_ARENA_Y, 11.0);
+        app.world_mut().get_mut::<Transform>(bullet).unwrap().translation = soul + offset.extend(0.0);
+    }
+
+    #[test]
+    fn bullet_that_passes_close_grazes_once_it_leaves() {
+        let (mut app, bullet) = dodge_app(Vec2::new(40.0, 0.0));
+        app.update();
+        assert!(written::<BulletGrazed>(&app).is_empty());
+
+        move_bullet(&mut app, bullet, Vec2::new(-60.0, 0.0));
+        app.update();
+        assert_eq!(written::<BulletGrazed>(&app).len(), 1);
+        assert_eq!(app.world().resource::<CurrentBattle>().graze_count, 1);
+    }
+
+    #[test]
+    fn bullet_that_grazes_then_hits_is_not_a_graze() {
+        let (mut app, bullet) = dodge_app(Vec2::new(40.0, 0.0));
+        app.update();
+        move_bullet(&mut app, bullet, Vec2::new(10.0, 0.0));
+        app.update();
+
+        assert_eq!(written::<PlayerDamaged>(&app).len(), 1);
+        assert!(written::<BulletGrazed>(&app).is_empty());
+        let battle = app.world().resource::<CurrentBattle>();
+        assert_eq!(battle.graze_count, 0);
+        assert_eq!(battle.graze_meter, 0.0);
     }
 }
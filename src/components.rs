@@ -1,4 +1,7 @@
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::ai::BulletPattern;
+use crate::enemies::MovementPath;
 
 #[derive(States, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub enum GameState {
@@ -10,6 +13,78 @@ pub enum GameState {
     Victory,
 }
 
+#[derive(SubStates, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[source(GameState = GameState::Overworld | GameState::Battle)]
+pub enum PauseState {
+    #[default]
+    Running,
+    Paused,
+}
+
+/// Battles open with a wipe into the arena and close with a fade and results card.
+/// Combat systems only run while `Active`.
+#[derive(SubStates, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[source(GameState = GameState::Battle)]
+pub enum BattleTransition {
+    #[default]
+    Entering,
+    Active,
+    Exiting,
+}
+
+#[derive(States, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum SettingsMenuState {
+    #[default]
+    Closed,
+    Open,
+}
+
+#[derive(States, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum DifficultyMenuState {
+    #[default]
+    Closed,
+    Open,
+}
+
+#[derive(States, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum LeaderboardState {
+    #[default]
+    Closed,
+    Open,
+}
+
+#[derive(States, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum AchievementsMenuState {
+    #[default]
+    Closed,
+    Open,
+}
+
+#[derive(States, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum QuestLogState {
+    #[default]
+    Closed,
+    Open,
+}
+
+/// A conversation is on screen; the overworld and battle systems wait for it to close.
+#[derive(States, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum DialogueState {
+    #[default]
+    Closed,
+    Open,
+}
+
+/// Who acts first when a battle opens.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Initiative {
+    Player,
+    /// The player slipped up behind the enemy and gets a free turn.
+    Preemptive,
+    /// The enemy caught the player and opens with its attack.
+    Ambushed,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum BattlePhase {
     Intro,
@@ -26,6 +101,29 @@ pub const TOTAL_ROOMS: usize = 6;
 pub const BATTLE_ARENA_Y: f32 = 250.0;
 pub const ARENA_WIDTH: f32 = 350.0;
 pub const ARENA_HEIGHT: f32 = 280.0;
+pub const ENCOUNTER_RADIUS: f32 = 40.0;
+pub const FLEE_PUSHBACK: f32 = 70.0;
+pub const GRAZE_RADIUS: f32 = 48.0;
+pub const GRAZE_PER_BULLET: f32 = 12.0;
+pub const GRAZE_METER_MAX: f32 = 100.0;
+pub const PERFECT_ACCURACY: f32 = 0.84;
+pub const GOOD_ACCURACY: f32 = 0.56;
+pub const COMBO_DAMAGE_STEP: f32 = 0.25;
+pub const MAX_STRIKES_PER_TURN: usize = 3;
+pub const SHIELD_DISTANCE: f32 = 24.0;
+pub const SHIELD_HALF_ARC: f32 = 0.8;
+pub const SHIELD_TURN_SPEED: f32 = 5.0;
+pub const PARRY_WINDOW: f32 = 0.15;
+pub const PARRY_GRAZE_REFUND: f32 = 25.0;
+pub const PLAYER_START: Vec3 = Vec3::new(0.0, -220.0, 1.0);
+pub const CHECKPOINT_ROOMS: [usize; 2] = [1, 3];
+pub const PATROL_SPEED: f32 = 40.0;
+pub const PATROL_HALF_WIDTH: f32 = 80.0;
+pub const CHASE_SPEED: f32 = 120.0;
+pub const VIEW_RANGE: f32 = 110.0;
+pub const VIEW_HALF_ANGLE: f32 = 0.6;
+pub const CHASE_GIVE_UP_SECS: f32 = 1.5;
+pub const CHASE_LEASH: f32 = 90.0;
 
 #[derive(Component)]
 pub struct Player {
@@ -35,10 +133,196 @@ pub struct Player {
 
 #[derive(Component)]
 pub struct Enemy {
+    pub name: &'static str,
     pub health: i32,
     pub max_health: i32,
     pub room_index: usize,
     pub attack_pattern: usize,
+    pub boss: bool,
+    pub elements: ElementTable,
+    pub bullet_element: Element,
+    pub patterns: &'static [BulletPattern],
+    pub movement: MovementPath,
+    pub contact_damage: i32,
+}
+
+impl Enemy {
+    /// Any enemy can be spared once worn down to half health or outlasted for three rounds.
+    pub fn can_spare(&self, rounds_survived: usize) -> bool {
+        self.health * 2 <= self.max_health || rounds_survived >= 3
+    }
+
+    /// Chance to escape this enemy, from 0.0 to 1.0. Bosses can't be fled.
+    pub fn flee_chance(&self, player: &Player) -> f32 {
+        if self.boss {
+            return 0.0;
+        }
+        let health_ratio = player.health.max(0) as f32 / player.max_health as f32;
+        (0.6 - self.room_index as f32 * 0.05 + (health_ratio - 0.5) * 0.3).clamp(0.1, 0.9)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Element {
+    Physical,
+    Fire,
+    Ice,
+    Shock,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Affinity {
+    Normal,
+    Weak,
+    Resist,
+}
+
+impl Affinity {
+    pub fn apply(&self, damage: i32) -> i32 {
+        match self {
+            Affinity::Normal => damage,
+            Affinity::Weak => (damage as f32 * 1.5).round() as i32,
+            Affinity::Resist => ((damage as f32 * 0.5).round() as i32).max(1),
+        }
+    }
+}
+
+/// Which elements hit harder or softer against a target.
+#[derive(Debug, Clone, Copy)]
+pub struct ElementTable {
+    pub weak: &'static [Element],
+    pub resist: &'static [Element],
+}
+
+impl ElementTable {
+    pub fn affinity(&self, element: Element) -> Affinity {
+        if self.weak.contains(&element) {
+            Affinity::Weak
+        } else if self.resist.contains(&element) {
+            Affinity::Resist
+        } else {
+            Affinity::Normal
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatusKind {
+    Poison,
+    Slow,
+    Burn,
+    Stun,
+    Shield,
+}
+
+impl StatusKind {
+    pub fn tag(&self) -> &'static str {
+        match self {
+            StatusKind::Poison => "PSN",
+            StatusKind::Slow => "SLW",
+            StatusKind::Burn => "BRN",
+            StatusKind::Stun => "STN",
+            StatusKind::Shield => "SHD",
+        }
+    }
+
+    /// Damage dealt at each turn boundary for damage-over-time effects.
+    pub fn damage_per_turn(&self, stacks: u32) -> i32 {
+        match self {
+            StatusKind::Poison => stacks as i32,
+            StatusKind::Burn => 2 * stacks as i32,
+            _ => 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StatusDuration {
+    Turns(u32),
+    Seconds(f32),
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct StatusEffect {
+    pub kind: StatusKind,
+    pub stacks: u32,
+    pub duration: StatusDuration,
+}
+
+#[derive(Component, Default)]
+pub struct StatusEffects {
+    pub effects: Vec<StatusEffect>,
+}
+
+impl StatusEffects {
+    /// Adds stacks of an effect, refreshing its duration if already present.
+    pub fn apply(&mut self, kind: StatusKind, stacks: u32, duration: StatusDuration) {
+        if let Some(effect) = self.effects.iter_mut().find(|e| e.kind == kind) {
+            effect.stacks += stacks;
+            effect.duration = duration;
+        } else {
+            self.effects.push(StatusEffect { kind, stacks, duration });
+        }
+    }
+
+    pub fn stacks(&self, kind: StatusKind) -> u32 {
+        self.effects.iter().find(|e| e.kind == kind).map_or(0, |e| e.stacks)
+    }
+
+    pub fn has(&self, kind: StatusKind) -> bool {
+        self.stacks(kind) > 0
+    }
+
+    pub fn remove(&mut self, kind: StatusKind) {
+        self.effects.retain(|e| e.kind != kind);
+    }
+
+    /// Runs at a turn boundary. Returns damage-over-time to apply and
+    /// counts down turn-based durations.
+    pub fn tick_turn(&mut self) -> i32 {
+        let damage = self.effects.iter().map(|e| e.kind.damage_per_turn(e.stacks)).sum();
+        for effect in self.effects.iter_mut() {
+            if let StatusDuration::Turns(turns) = &mut effect.duration {
+                *turns = turns.saturating_sub(1);
+            }
+        }
+        self.effects.retain(|e| e.duration != StatusDuration::Turns(0));
+        damage
+    }
+
+    pub fn tick_seconds(&mut self, dt: f32) {
+        for effect in self.effects.iter_mut() {
+            if let StatusDuration::Seconds(secs) = &mut effect.duration {
+                *secs -= dt;
+            }
+        }
+        self.effects.retain(|e| !matches!(e.duration, StatusDuration::Seconds(secs) if secs <= 0.0));
+    }
+
+    /// Soaks incoming damage with Shield stacks, returning what gets through.
+    pub fn absorb(&mut self, damage: i32) -> i32 {
+        let shield = self.stacks(StatusKind::Shield) as i32;
+        if shield == 0 {
+            return damage;
+        }
+        let absorbed = shield.min(damage);
+        if let Some(effect) = self.effects.iter_mut().find(|e| e.kind == StatusKind::Shield) {
+            effect.stacks -= absorbed as u32;
+        }
+        self.effects.retain(|e| e.stacks > 0);
+        damage - absorbed
+    }
+
+    pub fn summary(&self) -> String {
+        self.effects
+            .iter()
+            .map(|e| match e.duration {
+                StatusDuration::Turns(turns) => format!("[{}{} {}t]", e.kind.tag(), e.stacks, turns),
+                StatusDuration::Seconds(secs) => format!("[{}{} {:.1}s]", e.kind.tag(), e.stacks, secs),
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
 }
 
 #[derive(Component)]
@@ -52,6 +336,11 @@ pub struct Bullet {
     pub velocity: Vec2,
     pub damage: i32,
     pub lifetime: Timer,
+    /// Inside the graze radius; it pays out once the bullet leaves or despawns without hitting.
+    pub grazing: bool,
+    pub reflected: bool,
+    pub status: Option<StatusKind>,
+    pub element: Element,
 }
 
 #[derive(Component)]
@@ -63,6 +352,58 @@ pub struct PlayerSprite;
 #[derive(Component)]
 pub struct EnemySprite;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PatrolState {
+    Patrolling,
+    Chasing,
+    Returning,
+}
+
+/// Overworld behaviour for an enemy walking its room.
+#[derive(Component)]
+pub struct Patrol {
+    pub home: Vec3,
+    pub facing: Vec2,
+    pub state: PatrolState,
+    /// Seconds the player has been out of sight while chasing.
+    pub lost_for: f32,
+    /// Seconds before the enemy can notice the player again.
+    pub alert_cooldown: f32,
+}
+
+impl Patrol {
+    pub fn new(home: Vec3) -> Self {
+        Self {
+            home,
+            facing: Vec2::X,
+            state: PatrolState::Patrolling,
+            lost_for: 0.0,
+            alert_cooldown: 0.0,
+        }
+    }
+
+    pub fn can_see(&self, to_player: Vec2) -> bool {
+        self.alert_cooldown <= 0.0
+            && to_player.length() < VIEW_RANGE
+            && self.facing.angle_to(to_player).abs() < VIEW_HALF_ANGLE
+    }
+}
+
+/// Drives the battle sprite along its enemy's movement path.
+#[derive(Component)]
+pub struct ArenaMotion {
+    pub path: MovementPath,
+    pub home: Vec3,
+    pub elapsed: f32,
+}
+
+/// An enemy sprite that hurts the soul on touch, with a short grace period between hits.
+#[derive(Component)]
+pub struct ContactHazard {
+    pub damage: i32,
+    pub cooldown: f32,
+}
+
 #[derive(Component)]
 pub struct DamageNotif {
     pub timer: Timer,
@@ -75,9 +416,12 @@ pub struct ScreenShake {
 }
 
 #[derive(Component)]
-pub struct AttackIndicator {
-    pub speed: f32,
-    pub direction: f32,
+pub struct MinigameSprite;
+
+#[derive(Component)]
+pub struct Shield {
+    pub angle: f32,
+    pub parry_time_left: f32,
 }
 
 #[derive(Component)]
@@ -88,6 +432,13 @@ pub struct Telegraph {
 #[derive(Component)]
 pub struct ExitDoor;
 
+/// Touching one records it as the respawn point for later deaths.
+#[derive(Component)]
+pub struct Checkpoint {
+    pub index: usize,
+    pub activated: bool,
+}
+
 #[derive(Component)]
 pub struct BattleUI;
 
@@ -100,6 +451,9 @@ pub struct HealthText;
 #[derive(Component)]
 pub struct ControlsText;
 
+#[derive(Component)]
+pub struct ComboText;
+
 #[derive(Component)]
 pub struct OverworldInstructions;
 
@@ -112,12 +466,107 @@ pub struct MainMenuUI;
 #[derive(Component)]
 pub struct OverworldCamera;
 
+#[derive(Component)]
+pub struct PauseMenuUI;
+
+#[derive(Component)]
+pub struct PauseMenuItem(pub usize);
+
 #[derive(Component)]
 pub struct Particle {
     pub timer: Timer,
     pub velocity: Vec2,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Weapon {
+    Sword,
+    WarDrum,
+    Hammer,
+    Bow,
+    TwinBlades,
+}
+
+impl Weapon {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Weapon::Sword => "Sword",
+            Weapon::WarDrum => "War Drum",
+            Weapon::Hammer => "Hammer",
+            Weapon::Bow => "Bow",
+            Weapon::TwinBlades => "Twin Blades",
+        }
+    }
+
+    pub fn element(&self) -> Element {
+        match self {
+            Weapon::Sword => Element::Physical,
+            Weapon::WarDrum => Element::Shock,
+            Weapon::Hammer => Element::Ice,
+            Weapon::Bow => Element::Fire,
+            Weapon::TwinBlades => Element::Physical,
+        }
+    }
+
+    /// Status the weapon inflicts on a solid hit, if any.
+    pub fn on_hit_status(&self, accuracy: f32) -> Option<(StatusKind, u32, StatusDuration)> {
+        match self {
+            Weapon::TwinBlades if accuracy >= GOOD_ACCURACY => Some((StatusKind::Poison, 1, StatusDuration::Turns(3))),
+            Weapon::Hammer if accuracy >= PERFECT_ACCURACY => Some((StatusKind::Stun, 1, StatusDuration::Turns(1))),
+            _ => None,
+        }
+    }
+
+    pub fn next(&self) -> Weapon {
+        match self {
+            Weapon::Sword => Weapon::WarDrum,
+            Weapon::WarDrum => Weapon::Hammer,
+            Weapon::Hammer => Weapon::Bow,
+            Weapon::Bow => Weapon::TwinBlades,
+            Weapon::TwinBlades => Weapon::Sword,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Armor {
+    Leather,
+    Insulated,
+    Asbestos,
+}
+
+impl Armor {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Armor::Leather => "Leather",
+            Armor::Insulated => "Insulated",
+            Armor::Asbestos => "Asbestos",
+        }
+    }
+
+    pub fn elements(&self) -> ElementTable {
+        match self {
+            Armor::Leather => ElementTable { weak: &[], resist: &[Element::Physical] },
+            Armor::Insulated => ElementTable { weak: &[Element::Fire], resist: &[Element::Shock, Element::Ice] },
+            Armor::Asbestos => ElementTable { weak: &[Element::Shock], resist: &[Element::Fire] },
+        }
+    }
+
+    pub fn next(&self) -> Armor {
+        match self {
+            Armor::Leather => Armor::Insulated,
+            Armor::Insulated => Armor::Asbestos,
+            Armor::Asbestos => Armor::Leather,
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct Equipment {
+    pub weapon: Weapon,
+    pub armor: Armor,
+}
+
 #[derive(Resource)]
 pub struct CurrentBattle {
     pub enemy_entity: Entity,
@@ -125,6 +574,12 @@ pub struct CurrentBattle {
     pub phase_timer: Timer,
     pub player_defended: bool,
     pub combo_count: usize,
+    pub strikes_this_turn: usize,
+    pub graze_meter: f32,
+    pub graze_count: usize,
+    pub initiative: Initiative,
+    /// Enemy attack rounds the player has lived through this fight.
+    pub rounds_survived: usize,
 }
 
 #[derive(Resource)]
@@ -132,8 +587,81 @@ pub struct GameProgress {
     pub current_room: usize,
     pub rooms_cleared: usize,
     pub total_rooms: usize,
+    pub assisted: bool,
+}
+
+/// Standard runs respawn at the last checkpoint; permadeath restarts from scratch.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum RunMode {
+    #[default]
+    Standard,
+    Permadeath,
+}
+
+impl RunMode {
+    pub fn toggle(&self) -> RunMode {
+        match self {
+            RunMode::Standard => RunMode::Permadeath,
+            RunMode::Permadeath => RunMode::Standard,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            RunMode::Standard => "Standard (checkpoints)",
+            RunMode::Permadeath => "Permadeath",
+        }
+    }
 }
 
+/// Where the player reappears after a death in a standard run.
+#[derive(Resource)]
+pub struct RespawnPoint {
+    pub position: Vec3,
+}
+
+impl Default for RespawnPoint {
+    fn default() -> Self {
+        Self { position: PLAYER_START }
+    }
+}
+
+/// Items picked up during the run, in the order they were first found.
+#[derive(Resource, Default)]
+pub struct Inventory {
+    pub items: Vec<(String, u32)>,
+}
+
+impl Inventory {
+    pub fn add(&mut self, item: &str, amount: u32) {
+        match self.items.iter_mut().find(|(name, _)| name == item) {
+            Some((_, count)) => *count += amount,
+            None => self.items.push((item.to_string(), amount)),
+        }
+    }
+
+    pub fn count(&self, item: &str) -> u32 {
+        self.items.iter().find(|(name, _)| name == item).map_or(0, |(_, count)| *count)
+    }
+
+    /// Takes up to `amount` of an item, dropping its slot once none are left.
+    pub fn remove(&mut self, item: &str, amount: u32) {
+        if let Some(slot) = self.items.iter_mut().find(|(name, _)| name == item) {
+            slot.1 = slot.1.saturating_sub(amount);
+        }
+        self.items.retain(|(_, count)| *count > 0);
+    }
+}
+
+#[derive(Component)]
+pub struct Npc {
+    pub name: String,
+    pub dialogue: String,
+}
+
+#[derive(Component)]
+pub struct TalkPrompt;
+
 #[derive(Resource)]
 pub struct BulletSpawner {
     pub timer: Timer,
@@ -0,0 +1,441 @@
+use std::collections::HashMap;
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use serde::Deserialize;
+use crate::components::*;
+use crate::flags::GameFlags;
+
+const DIALOGUE_PATH: &str = "dialogue.ron";
+const TALK_RADIUS: f32 = 40.0;
+const CHARS_PER_SEC: f32 = 40.0;
+
+#[derive(Debug, Clone, Deserialize)]
+pub enum Condition {
+    RoomsCleared(usize),
+    HasItem(String, u32),
+    Flag(String),
+    NotFlag(String),
+    CounterAtLeast(String, i32),
+    TextIs(String, String),
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub enum Effect {
+    GiveItem(String, u32),
+    TakeItem(String, u32),
+    SetFlag(String),
+    ClearFlag(String),
+    AddCounter(String, i32),
+    SetText(String, String),
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Branch {
+    #[serde(default)]
+    pub conditions: Vec<Condition>,
+    pub node: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Choice {
+    pub text: String,
+    #[serde(default)]
+    pub conditions: Vec<Condition>,
+    #[serde(default)]
+    pub effects: Vec<Effect>,
+    #[serde(default)]
+    pub next: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct DialogueNode {
+    pub speaker: String,
+    pub text: String,
+    #[serde(default)]
+    pub effects: Vec<Effect>,
+    #[serde(default)]
+    pub choices: Vec<Choice>,
+    #[serde(default)]
+    pub next: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct DialogueTree {
+    pub start: Vec<Branch>,
+    pub nodes: HashMap<String, DialogueNode>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct NpcDef {
+    pub name: String,
+    pub position: (f32, f32),
+    pub color: (f32, f32, f32),
+    pub dialogue: String,
+}
+
+/// Trees an enemy opens with and cuts in with at half health.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct Banter {
+    pub pre_battle: Option<String>,
+    pub mid_battle: Option<String>,
+}
+
+#[derive(Resource, Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct Dialogues {
+    pub npcs: Vec<NpcDef>,
+    pub trees: HashMap<String, DialogueTree>,
+    pub banter: HashMap<String, Banter>,
+}
+
+impl Dialogues {
+    pub fn load() -> Self {
+        crate::persist::load_asset_ron(DIALOGUE_PATH)
+    }
+
+    fn node(&self, tree: &str, node: &str) -> Option<&DialogueNode> {
+        self.trees.get(tree)?.nodes.get(node)
+    }
+}
+
+/// The conversation on screen: which node is showing and how much of it has typed out.
+#[derive(Resource, Default)]
+pub struct ActiveDialogue {
+    pub tree: String,
+    pub node: String,
+    pub revealed: f32,
+    pub selected: usize,
+}
+
+/// Whether this battle's half-health banter has already played.
+#[derive(Resource, Default)]
+pub struct BanterTracker {
+    pub mid_battle_played: bool,
+}
+
+#[derive(Component)]
+pub struct DialogueBoxUI;
+
+#[derive(Component)]
+pub struct DialogueSpeakerText;
+
+#[derive(Component)]
+pub struct DialogueBodyText;
+
+#[derive(Component)]
+pub struct DialogueChoicesText;
+
+/// Everything a conversation reads and writes, bundled so callers can open and step trees.
+#[derive(SystemParam)]
+pub struct DialogueRunner<'w> {
+    dialogues: Res<'w, Dialogues>,
+    active: ResMut<'w, ActiveDialogue>,
+    game_progress: Res<'w, GameProgress>,
+    inventory: ResMut<'w, Inventory>,
+    flags: ResMut<'w, GameFlags>,
+    dialogue_state: ResMut<'w, NextState<DialogueState>>,
+}
+
+pub fn conditions_met(conditions: &[Condition], game_progress: &GameProgress, inventory: &Inventory, flags: &GameFlags) -> bool {
+    conditions.iter().all(|condition| match condition {
+        Condition::RoomsCleared(rooms) => game_progress.rooms_cleared >= *rooms,
+        Condition::HasItem(item, count) => inventory.count(item) >= *count,
+        Condition::Flag(flag) => flags.is_set(flag),
+        Condition::NotFlag(flag) => !flags.is_set(flag),
+        Condition::CounterAtLeast(name, value) => flags.counter(name) >= *value,
+        Condition::TextIs(name, value) => flags.text(name) == Some(value.as_str()),
+    })
+}
+
+pub fn apply_effects(effects: &[Effect], inventory: &mut Inventory, flags: &mut GameFlags) {
+    for effect in effects {
+        match effect {
+            Effect::GiveItem(item, count) => inventory.add(item, *count),
+            Effect::TakeItem(item, count) => inventory.remove(item, *count),
+            Effect::SetFlag(flag) => flags.set_bool(flag, true),
+            Effect::ClearFlag(flag) => flags.clear(flag),
+            Effect::AddCounter(name, amount) => flags.add_counter(name, *amount),
+            Effect::SetText(name, value) => flags.set_text(name, value),
+        }
+    }
+}
+
+impl DialogueRunner<'_> {
+    fn met(&self, conditions: &[Condition]) -> bool {
+        conditions_met(conditions, &self.game_progress, &self.inventory, &self.flags)
+    }
+
+    /// Opens a tree at its first branch whose conditions hold. Returns false if none do.
+    pub fn start(&mut self, tree: &str) -> bool {
+        let Some(dialogue) = self.dialogues.trees.get(tree) else {
+            warn!("Unknown dialogue tree '{}'", tree);
+            return false;
+        };
+        let Some(branch) = dialogue.start.iter().find(|branch| self.met(&branch.conditions)) else {
+            return false;
+        };
+        let node = branch.node.clone();
+        self.active.tree = tree.to_string();
+        self.enter(node);
+        self.dialogue_state.set(DialogueState::Open);
+        true
+    }
+
+    /// Shows a node and applies its effects.
+    fn enter(&mut self, node: String) {
+        self.active.node = node;
+        self.active.revealed = 0.0;
+        self.active.selected = 0;
+        if let Some(node) = self.dialogues.node(&self.active.tree, &self.active.node) {
+            apply_effects(&node.effects, &mut self.inventory, &mut self.flags);
+        }
+    }
+
+    fn current(&self) -> Option<&DialogueNode> {
+        self.dialogues.node(&self.active.tree, &self.active.node)
+    }
+
+    fn visible_choices(&self) -> Vec<&Choice> {
+        self.current()
+            .map(|node| node.choices.iter().filter(|choice| self.met(&choice.conditions)).collect())
+            .unwrap_or_default()
+    }
+
+    /// Moves past the current node: takes the selected choice, follows `next`, or closes.
+    fn advance(&mut self) {
+        let Some(node) = self.dialogues.node(&self.active.tree, &self.active.node) else {
+            self.dialogue_state.set(DialogueState::Closed);
+            return;
+        };
+
+        let choice = node
+            .choices
+            .iter()
+            .filter(|choice| conditions_met(&choice.conditions, &self.game_progress, &self.inventory, &self.flags))
+            .nth(self.active.selected);
+        let next = match choice {
+            Some(choice) => {
+                apply_effects(&choice.effects, &mut self.inventory, &mut self.flags);
+                choice.next.clone()
+            }
+            None => node.next.clone(),
+        };
+        match next {
+            Some(next) => self.enter(next),
+            None => self.dialogue_state.set(DialogueState::Closed),
+        }
+    }
+}
+
+pub fn spawn_npcs(mut commands: Commands, dialogues: Res<Dialogues>) {
+    for npc in dialogues.npcs.iter() {
+        let (r, g, b) = npc.color;
+        commands
+            .spawn((
+                Sprite {
+                    color: Color::srgb(r, g, b),
+                    custom_size: Some(Vec2::new(20.0, 20.0)),
+                    ..default()
+                },
+                Transform::from_translation(Vec3::new(npc.position.0, npc.position.1, 0.5)),
+                Npc { name: npc.name.clone(), dialogue: npc.dialogue.clone() },
+            ))
+            .with_children(|parent| {
+                parent.spawn((
+                    Text2d::new(format!("{}\n[SPACE] Talk", npc.name)),
+                    TextFont { font_size: 11.0, ..default() },
+                    TextColor(Color::WHITE),
+                    TextLayout::new_with_justify(Justify::Center),
+                    Transform::from_translation(Vec3::new(0.0, 26.0, 1.0)),
+                    Visibility::Hidden,
+                    TalkPrompt,
+                ));
+            });
+    }
+}
+
+pub fn update_talk_prompts(
+    player_query: Query<&Transform, (With<Player>, Without<PlayerSprite>)>,
+    npc_query: Query<(&Transform, &Children), With<Npc>>,
+    mut prompt_query: Query<&mut Visibility, With<TalkPrompt>>,
+) {
+    let Ok(player_transform) = player_query.single() else { return };
+    for (transform, children) in npc_query.iter() {
+        let near = player_transform.translation.distance(transform.translation) < TALK_RADIUS;
+        for child in children.iter() {
+            if let Ok(mut visibility) = prompt_query.get_mut(child) {
+                *visibility = if near { Visibility::Inherited } else { Visibility::Hidden };
+            }
+        }
+    }
+}
+
+pub fn talk_to_npc(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    player_query: Query<&Transform, (With<Player>, Without<PlayerSprite>)>,
+    npc_query: Query<(&Transform, &Npc)>,
+    mut runner: DialogueRunner,
+) {
+    if !(keyboard.just_pressed(KeyCode::Space) || keyboard.just_pressed(KeyCode::Enter)) {
+        return;
+    }
+    let Ok(player_transform) = player_query.single() else { return };
+
+    let nearest = npc_query
+        .iter()
+        .map(|(transform, npc)| (player_transform.translation.distance(transform.translation), npc))
+        .filter(|(distance, _)| *distance < TALK_RADIUS)
+        .min_by(|a, b| a.0.total_cmp(&b.0));
+    if let Some((_, npc)) = nearest {
+        if !runner.start(&npc.dialogue) {
+            info!("{} has nothing to say", npc.name);
+        }
+    }
+}
+
+pub fn reset_banter(mut tracker: ResMut<BanterTracker>) {
+    tracker.mid_battle_played = false;
+}
+
+/// Plays the enemy's opening lines once the arena is revealed.
+pub fn pre_battle_banter(battle_state: Res<CurrentBattle>, enemy_query: Query<&Enemy>, mut runner: DialogueRunner) {
+    let Ok(enemy) = enemy_query.get(battle_state.enemy_entity) else { return };
+    let tree = runner.dialogues.banter.get(enemy.name).and_then(|banter| banter.pre_battle.clone());
+    if let Some(tree) = tree {
+        runner.start(&tree);
+    }
+}
+
+/// Cuts in once the enemy drops to half health, waiting for the player's turn so no bullets are in flight.
+pub fn mid_battle_banter(
+    battle_state: Res<CurrentBattle>,
+    enemy_query: Query<&Enemy>,
+    mut tracker: ResMut<BanterTracker>,
+    mut runner: DialogueRunner,
+) {
+    if tracker.mid_battle_played || battle_state.phase != BattlePhase::PlayerTurn {
+        return;
+    }
+    let Ok(enemy) = enemy_query.get(battle_state.enemy_entity) else { return };
+    if enemy.health <= 0 || enemy.health * 2 > enemy.max_health {
+        return;
+    }
+
+    tracker.mid_battle_played = true;
+    let tree = runner.dialogues.banter.get(enemy.name).and_then(|banter| banter.mid_battle.clone());
+    if let Some(tree) = tree {
+        runner.start(&tree);
+    }
+}
+
+pub fn dialogue_input(keyboard: Res<ButtonInput<KeyCode>>, time: Res<Time>, mut runner: DialogueRunner) {
+    let Some(length) = runner.current().map(|node| node.text.chars().count() as f32) else {
+        runner.dialogue_state.set(DialogueState::Closed);
+        return;
+    };
+    runner.active.revealed = (runner.active.revealed + time.delta_secs() * CHARS_PER_SEC).min(length);
+    let typed_out = runner.active.revealed >= length;
+
+    let choices = runner.visible_choices().len();
+    if typed_out && choices > 0 {
+        if keyboard.just_pressed(KeyCode::KeyW) || keyboard.just_pressed(KeyCode::ArrowUp) {
+            runner.active.selected = (runner.active.selected + choices - 1) % choices;
+        }
+        if keyboard.just_pressed(KeyCode::KeyS) || keyboard.just_pressed(KeyCode::ArrowDown) {
+            runner.active.selected = (runner.active.selected + 1) % choices;
+        }
+    }
+
+    if keyboard.just_pressed(KeyCode::Space) || keyboard.just_pressed(KeyCode::Enter) {
+        if typed_out {
+            runner.advance();
+        } else {
+            runner.active.revealed = length;
+        }
+    }
+}
+
+pub fn open_dialogue_box(mut commands: Commands) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(20.0),
+                left: Val::Percent(10.0),
+                width: Val::Percent(80.0),
+                min_height: Val::Px(120.0),
+                padding: UiRect::all(Val::Px(14.0)),
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(8.0),
+                border: UiRect::all(Val::Px(2.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.03, 0.03, 0.08, 0.95)),
+            BorderColor::all(Color::srgb(0.8, 0.8, 0.9)),
+            GlobalZIndex(7),
+            DialogueBoxUI,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(""),
+                TextFont { font_size: 18.0, ..default() },
+                TextColor(Color::srgb(1.0, 0.85, 0.4)),
+                DialogueSpeakerText,
+            ));
+            parent.spawn((
+                Text::new(""),
+                TextFont { font_size: 20.0, ..default() },
+                TextColor(Color::WHITE),
+                DialogueBodyText,
+            ));
+            parent.spawn((
+                Text::new(""),
+                TextFont { font_size: 18.0, ..default() },
+                TextColor(Color::srgb(0.7, 0.9, 1.0)),
+                DialogueChoicesText,
+            ));
+        });
+}
+
+pub fn close_dialogue_box(mut commands: Commands, query: Query<Entity, With<DialogueBoxUI>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+pub fn update_dialogue_box(
+    runner: DialogueRunner,
+    mut speaker_query: Query<&mut Text, (With<DialogueSpeakerText>, Without<DialogueBodyText>)>,
+    mut body_query: Query<&mut Text, (With<DialogueBodyText>, Without<DialogueChoicesText>)>,
+    mut choices_query: Query<&mut Text, (With<DialogueChoicesText>, Without<DialogueSpeakerText>)>,
+) {
+    let Some(node) = runner.current() else { return };
+    let length = node.text.chars().count();
+    let shown = runner.active.revealed as usize;
+
+    if let Ok(mut text) = speaker_query.single_mut() {
+        **text = node.speaker.clone();
+    }
+    if let Ok(mut text) = body_query.single_mut() {
+        **text = node.text.chars().take(shown).collect();
+    }
+    if let Ok(mut text) = choices_query.single_mut() {
+        let choices = runner.visible_choices();
+        **text = if shown < length {
+            String::new()
+        } else if choices.is_empty() {
+            "▼ [SPACE]".to_string()
+        } else {
+            choices
+                .iter()
+                .enumerate()
+                .map(|(i, choice)| {
+                    let cursor = if i == runner.active.selected { "▶" } else { " " };
+                    format!("{} {}", cursor, choice.text)
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+    }
+}
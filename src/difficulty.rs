@@ -0,0 +1,227 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::components::*;
+use crate::enemies::ENEMY_ROSTER;
+
+const DIFFICULTY_PATH: &str = "difficulty.ron";
+const DIFFICULTY_ROWS: usize = 7;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum DifficultyPreset {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+    Nightmare,
+    Custom,
+}
+
+impl DifficultyPreset {
+    fn next(&self) -> DifficultyPreset {
+        match self {
+            DifficultyPreset::Easy => DifficultyPreset::Normal,
+            DifficultyPreset::Normal => DifficultyPreset::Hard,
+            DifficultyPreset::Hard => DifficultyPreset::Nightmare,
+            DifficultyPreset::Nightmare => DifficultyPreset::Easy,
+            DifficultyPreset::Custom => DifficultyPreset::Normal,
+        }
+    }
+
+    fn prev(&self) -> DifficultyPreset {
+        match self {
+            DifficultyPreset::Easy => DifficultyPreset::Nightmare,
+            DifficultyPreset::Normal => DifficultyPreset::Easy,
+            DifficultyPreset::Hard => DifficultyPreset::Normal,
+            DifficultyPreset::Nightmare => DifficultyPreset::Hard,
+            DifficultyPreset::Custom => DifficultyPreset::Normal,
+        }
+    }
+}
+
+/// Multipliers applied on top of the base tuning numbers. 1.0 everywhere is Normal.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Difficulty {
+    pub preset: DifficultyPreset,
+    pub enemy_health: f32,
+    pub bullet_damage: f32,
+    pub bullet_speed: f32,
+    /// Length of the `BulletHell` dodge phase.
+    pub dodge_duration: f32,
+    /// Above 1.0 the attack minigames forgive more error.
+    pub timing_window: f32,
+    /// Time between bullet volleys; lower fires more often.
+    pub spawn_interval: f32,
+}
+
+impl Default for Difficulty {
+    fn default() -> Self {
+        Self::preset(DifficultyPreset::Normal)
+    }
+}
+
+impl Difficulty {
+    pub fn preset(preset: DifficultyPreset) -> Self {
+        let (enemy_health, bullet_damage, bullet_speed, dodge_duration, timing_window, spawn_interval) = match preset {
+            DifficultyPreset::Easy => (0.75, 0.5, 0.8, 0.75, 1.4, 1.3),
+            DifficultyPreset::Normal | DifficultyPreset::Custom => (1.0, 1.0, 1.0, 1.0, 1.0, 1.0),
+            DifficultyPreset::Hard => (1.25, 1.5, 1.2, 1.25, 0.8, 0.8),
+            DifficultyPreset::Nightmare => (1.5, 2.0, 1.4, 1.5, 0.6, 0.6),
+        };
+        Self { preset, enemy_health, bullet_damage, bullet_speed, dodge_duration, timing_window, spawn_interval }
+    }
+
+    pub fn load() -> Self {
+        crate::persist::load_ron(DIFFICULTY_PATH)
+    }
+
+    pub fn save(&self) {
+        crate::persist::save_ron(DIFFICULTY_PATH, self);
+    }
+
+    pub fn label(&self) -> String {
+        format!("{:?}", self.preset)
+    }
+
+    /// Averages how much each knob tilts the game against the player; Normal scores 1.0.
+    pub fn score_multiplier(&self) -> f32 {
+        (self.enemy_health
+            + self.bullet_damage
+            + self.bullet_speed
+            + self.dodge_duration
+            + 1.0 / self.timing_window
+            + 1.0 / self.spawn_interval)
+            / 6.0
+    }
+
+    pub fn scale_damage(&self, damage: i32) -> i32 {
+        ((damage as f32 * self.bullet_damage).round() as i32).max(1)
+    }
+
+    /// Stretches or squeezes the distance from a perfect hit so every rating threshold moves with it.
+    pub fn adjust_accuracy(&self, accuracy: f32) -> f32 {
+        (1.0 - (1.0 - accuracy) / self.timing_window).clamp(0.0, 1.0)
+    }
+
+    fn row_label(&self, row: usize) -> String {
+        match row {
+            0 => format!("Preset: {:?}", self.preset),
+            1 => format!("Enemy Health: {:.0}%", self.enemy_health * 100.0),
+            2 => format!("Bullet Damage: {:.0}%", self.bullet_damage * 100.0),
+            3 => format!("Bullet Speed: {:.0}%", self.bullet_speed * 100.0),
+            4 => format!("Dodge Phase Length: {:.0}%", self.dodge_duration * 100.0),
+            5 => format!("Timing Window: {:.0}%", self.timing_window * 100.0),
+            _ => format!("Volley Interval: {:.0}%", self.spawn_interval * 100.0),
+        }
+    }
+
+    fn adjust(&mut self, row: usize, step: f32) {
+        let knob = match row {
+            0 => {
+                let preset = if step < 0.0 { self.preset.prev() } else { self.preset.next() };
+                *self = Difficulty::preset(preset);
+                return;
+            }
+            1 => &mut self.enemy_health,
+            2 => &mut self.bullet_damage,
+            3 => &mut self.bullet_speed,
+            4 => &mut self.dodge_duration,
+            5 => &mut self.timing_window,
+            _ => &mut self.spawn_interval,
+        };
+        *knob = (*knob + step * 0.05).clamp(0.25, 3.0);
+        self.preset = DifficultyPreset::Custom;
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct DifficultyMenu {
+    pub selected: usize,
+}
+
+#[derive(Component)]
+pub struct DifficultyMenuUI;
+
+#[derive(Component)]
+pub struct DifficultyRow(pub usize);
+
+/// Scales every enemy's health to the chosen difficulty as a run begins.
+pub fn apply_enemy_health(difficulty: Res<Difficulty>, mut enemy_query: Query<&mut Enemy>) {
+    for mut enemy in enemy_query.iter_mut() {
+        let base = ENEMY_ROSTER[enemy.room_index].health as f32;
+        enemy.max_health = ((base * difficulty.enemy_health).round() as i32).max(1);
+        enemy.health = enemy.max_health;
+    }
+}
+
+pub fn open_difficulty_menu(mut commands: Commands, mut menu: ResMut<DifficultyMenu>, difficulty: Res<Difficulty>) {
+    menu.selected = 0;
+
+    crate::overlay::spawn_overlay(
+        &mut commands,
+        DifficultyMenuUI,
+        "DIFFICULTY",
+        "[W/S] Select | [A/D] Change | [ESC] Back\nChanging any multiplier switches to a Custom preset",
+        |parent| {
+            for row in 0..DIFFICULTY_ROWS {
+                parent.spawn((
+                    Text::new(difficulty.row_label(row)),
+                    TextFont { font_size: 20.0, ..default() },
+                    TextColor(Color::srgb(0.7, 0.7, 0.7)),
+                    DifficultyRow(row),
+                ));
+            }
+        },
+    );
+}
+
+pub fn close_difficulty_menu(
+    mut commands: Commands,
+    difficulty: Res<Difficulty>,
+    query: Query<Entity, With<DifficultyMenuUI>>,
+) {
+    difficulty.save();
+    for entity in query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+pub fn difficulty_menu_input(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut menu: ResMut<DifficultyMenu>,
+    mut difficulty: ResMut<Difficulty>,
+    mut menu_state: ResMut<NextState<DifficultyMenuState>>,
+) {
+    if crate::overlay::back_pressed(&keyboard) {
+        menu_state.set(DifficultyMenuState::Closed);
+        return;
+    }
+
+    if keyboard.just_pressed(KeyCode::KeyW) || keyboard.just_pressed(KeyCode::ArrowUp) {
+        menu.selected = (menu.selected + DIFFICULTY_ROWS - 1) % DIFFICULTY_ROWS;
+    }
+    if keyboard.just_pressed(KeyCode::KeyS) || keyboard.just_pressed(KeyCode::ArrowDown) {
+        menu.selected = (menu.selected + 1) % DIFFICULTY_ROWS;
+    }
+    if keyboard.just_pressed(KeyCode::KeyA) || keyboard.just_pressed(KeyCode::ArrowLeft) {
+        difficulty.adjust(menu.selected, -1.0);
+    }
+    if keyboard.just_pressed(KeyCode::KeyD) || keyboard.just_pressed(KeyCode::ArrowRight) {
+        difficulty.adjust(menu.selected, 1.0);
+    }
+}
+
+pub fn update_difficulty_menu(
+    menu: Res<DifficultyMenu>,
+    difficulty: Res<Difficulty>,
+    mut rows: Query<(&DifficultyRow, &mut Text, &mut TextColor)>,
+) {
+    for (row, mut text, mut color) in rows.iter_mut() {
+        **text = difficulty.row_label(row.0);
+        color.0 = if row.0 == menu.selected {
+            Color::srgb(1.0, 0.9, 0.3)
+        } else {
+            Color::srgb(0.7, 0.7, 0.7)
+        };
+    }
+}
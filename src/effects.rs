@@ -1,5 +1,101 @@
 use bevy::prelude::*;
 use crate::components::*;
+use crate::events::*;
+use crate::settings::Settings;
+use crate::transitions::BattleTransitionFx;
+use rand::Rng;
+
+const ENEMY_LABEL_POS: Vec3 = Vec3::new(80.0, BATTLE_ARENA_Y + 80.0, 15.0);
+const PLAYER_LABEL_POS: Vec3 = Vec3::new(-100.0, BATTLE_ARENA_Y - 50.0, 15.0);
+const STATUS_COLOR: Color = Color::srgb(0.7, 0.3, 0.9);
+
+pub fn on_enemy_damaged(
+    mut commands: Commands,
+    mut damaged: MessageReader<EnemyDamaged>,
+    mut shake_query: Query<&mut ScreenShake>,
+    settings: Res<Settings>,
+) {
+    for hit in damaged.read() {
+        match hit.source {
+            DamageSource::Strike => {
+                let Some(rating) = hit.rating else { continue };
+                let mut label = rating.label().to_string();
+                if hit.combo >= 2 {
+                    label = format!("{}\nx{} COMBO", label, hit.combo);
+                }
+                if hit.powered {
+                    label = format!("{}\n✦ POWERED ✦", label);
+                }
+                let color = rating.color();
+                spawn_damage(&mut commands, format!("{}\n-{}", label, hit.amount), ENEMY_LABEL_POS, color, hit.affinity);
+                spawn_particles(&mut commands, &settings, hit.position, color, 12);
+
+                if let Ok(mut shake) = shake_query.single_mut() {
+                    let base = if rating == Rating::Perfect { 0.6 } else { 0.3 };
+                    shake.trauma = (base + hit.combo as f32 * 0.08).min(1.0);
+                }
+            }
+            DamageSource::Reflect => {
+                let color = Color::srgb(0.3, 0.8, 1.0);
+                spawn_damage(&mut commands, format!("REFLECT\n-{}", hit.amount), ENEMY_LABEL_POS, color, hit.affinity);
+                spawn_particles(&mut commands, &settings, hit.position, color, 8);
+            }
+            DamageSource::Bullet | DamageSource::Contact | DamageSource::Status => {
+                spawn_damage(&mut commands, format!("-{}", hit.amount), ENEMY_LABEL_POS, STATUS_COLOR, hit.affinity);
+            }
+        }
+    }
+}
+
+pub fn on_player_damaged(mut commands: Commands, mut damaged: MessageReader<PlayerDamaged>, settings: Res<Settings>) {
+    for hit in damaged.read() {
+        if hit.source == DamageSource::Status {
+            spawn_damage(&mut commands, format!("-{}", hit.amount), PLAYER_LABEL_POS, STATUS_COLOR, hit.affinity);
+        } else {
+            spawn_damage(&mut commands, format!("-{}", hit.amount), PLAYER_LABEL_POS, Color::srgb(1.0, 0.6, 0.3), hit.affinity);
+            spawn_particles(&mut commands, &settings, hit.position, Color::srgb(1.0, 0.7, 0.3), 10);
+        }
+    }
+}
+
+pub fn on_bullet_deflected(
+    mut commands: Commands,
+    mut deflected: MessageReader<BulletDeflected>,
+    settings: Res<Settings>,
+) {
+    for deflect in deflected.read() {
+        if deflect.parried {
+            spawn_text(&mut commands, "PARRY!", Vec3::new(0.0, BATTLE_ARENA_Y - 20.0, 15.0), Color::srgb(0.6, 1.0, 1.0));
+            spawn_particles(&mut commands, &settings, deflect.position, Color::srgb(0.6, 1.0, 1.0), 10);
+        } else {
+            spawn_particles(&mut commands, &settings, deflect.position, Color::srgb(0.3, 0.8, 1.0), 4);
+        }
+    }
+}
+
+pub fn on_bullet_grazed(mut commands: Commands, mut grazed: MessageReader<BulletGrazed>, settings: Res<Settings>) {
+    for graze in grazed.read() {
+        spawn_particles(&mut commands, &settings, graze.position, Color::srgb(0.7, 0.9, 1.0), 4);
+    }
+}
+
+pub fn on_battle_notice(mut commands: Commands, mut notices: MessageReader<BattleNotice>) {
+    let center = Vec3::new(0.0, BATTLE_ARENA_Y + 10.0, 15.0);
+    let over_enemy = Vec3::new(80.0, BATTLE_ARENA_Y + 40.0, 15.0);
+
+    for notice in notices.read() {
+        let (text, pos, color) = match notice {
+            BattleNotice::EnemyStunned => ("STUNNED!", over_enemy, Color::srgb(1.0, 1.0, 0.5)),
+            BattleNotice::CaughtOffGuard => ("CAUGHT OFF GUARD!", over_enemy, Color::srgb(1.0, 1.0, 0.5)),
+            BattleNotice::PlayerStunned => ("STUNNED!", center, Color::srgb(1.0, 1.0, 0.5)),
+            BattleNotice::Defending => ("⚔ DEFENDING ⚔", center, Color::srgb(0.3, 0.8, 1.0)),
+            BattleNotice::CantEscape => ("CAN'T ESCAPE!", center, Color::srgb(1.0, 0.3, 0.3)),
+            BattleNotice::FleeFailed => ("Couldn't escape!", center, Color::srgb(1.0, 0.6, 0.3)),
+            BattleNotice::NotReadyToYield => ("Not ready to yield...", center, Color::srgb(0.8, 0.8, 0.8)),
+        };
+        spawn_text(&mut commands, text, pos, color);
+    }
+}
 
 pub fn update_damage_notifs(
     mut commands: Commands,
@@ -43,15 +139,19 @@ pub fn update_screen_shake(
     mut query: Query<(&mut Transform, &mut ScreenShake), With<OverworldCamera>>,
     player_query: Query<&Transform, (With<Player>, Without<OverworldCamera>)>,
     game_state: Res<State<GameState>>,
+    settings: Res<Settings>,
+    transition_fx: Res<BattleTransitionFx>,
 ) {
     let Ok((mut camera_transform, mut shake)) = query.single_mut() else { return };
     shake.trauma = (shake.trauma - time.delta_secs() * 2.5).max(0.0);
 
-    let shake_amount = shake.trauma * shake.trauma;
+    let intensity = if settings.reduce_motion { 0.0 } else { settings.screen_shake };
+    let shake_amount = shake.trauma * shake.trauma * intensity;
     let offset_x = (time.elapsed_secs() * 22.0).sin() * shake_amount * 10.0;
     let offset_y = (time.elapsed_secs() * 28.0).cos() * shake_amount * 10.0;
 
-    if *game_state.get() == GameState::Battle {
+    // The camera stays on the overworld until the entry wipe has covered the screen
+    if *game_state.get() == GameState::Battle && transition_fx.revealed {
         camera_transform.translation = Vec3::new(offset_x, BATTLE_ARENA_Y + offset_y, camera_transform.translation.z);
     } else if let Ok(player_transform) = player_query.single() {
         camera_transform.translation.x = offset_x;
@@ -61,41 +161,61 @@ pub fn update_screen_shake(
 
 pub fn update_battle_ui(
     battle_state: Res<CurrentBattle>,
-    player_query: Query<&Player, With<PlayerSprite>>,
-    enemy_query: Query<&Enemy>,
+    player_query: Query<(&Player, &StatusEffects), With<PlayerSprite>>,
+    enemy_query: Query<(&Enemy, &StatusEffects), Without<PlayerSprite>>,
     mut text_query: Query<&mut Text, With<HealthText>>,
 ) {
     let Ok(mut text) = text_query.single_mut() else { return };
-    let Ok(player) = player_query.single() else { return };
-    let Ok(enemy) = enemy_query.get(battle_state.enemy_entity) else { return };
+    let Ok((player, player_status)) = player_query.single() else { return };
+    let Ok((enemy, enemy_status)) = enemy_query.get(battle_state.enemy_entity) else { return };
+
+    let filled = ((battle_state.graze_meter / GRAZE_METER_MAX) * 10.0).floor() as usize;
+    let meter = format!("{}{}", "#".repeat(filled), "-".repeat(10 - filled));
+    let ready = if battle_state.graze_meter >= GRAZE_METER_MAX { " READY!" } else { "" };
 
     **text = format!(
-        "♥ Player: {}/{}\n◆ Enemy: {}/{}",
+        "♥ Player: {}/{} {}\n◆ {}: {}/{} {}\n✦ Graze: [{}]{}",
         player.health.max(0),
         player.max_health,
+        player_status.summary(),
+        enemy.name,
         enemy.health.max(0),
-        enemy.max_health
+        enemy.max_health,
+        enemy_status.summary(),
+        meter,
+        ready
     );
 }
 
 pub fn update_phase_text(
     battle_state: Res<CurrentBattle>,
+    settings: Res<Settings>,
     mut query: Query<(&mut Text, &mut TextColor), With<PhaseText>>,
 ) {
     let Ok((mut text, mut color)) = query.single_mut() else { return };
 
     match battle_state.phase {
-        BattlePhase::Intro => {
-            **text = "READY!".to_string();
-            color.0 = Color::srgb(1.0, 1.0, 1.0);
-        }
+        BattlePhase::Intro => match battle_state.initiative {
+            Initiative::Player => {
+                **text = "READY!".to_string();
+                color.0 = Color::srgb(1.0, 1.0, 1.0);
+            }
+            Initiative::Preemptive => {
+                **text = "PREEMPTIVE STRIKE!".to_string();
+                color.0 = settings.palette.positive();
+            }
+            Initiative::Ambushed => {
+                **text = "AMBUSHED!".to_string();
+                color.0 = settings.palette.danger();
+            }
+        },
         BattlePhase::PlayerTurn => {
             **text = "YOUR TURN".to_string();
-            color.0 = Color::srgb(0.3, 1.0, 0.3);
+            color.0 = settings.palette.positive();
         }
         BattlePhase::EnemyTelegraph => {
             **text = "INCOMING!".to_string();
-            color.0 = Color::srgb(1.0, 0.3, 0.3);
+            color.0 = settings.palette.danger();
         }
         BattlePhase::BulletHell => {
             **text = "DODGE!".to_string();
@@ -107,10 +227,93 @@ pub fn update_phase_text(
     }
 }
 
+pub fn update_combo_text(
+    battle_state: Res<CurrentBattle>,
+    settings: Res<Settings>,
+    mut query: Query<(&mut Text, &mut TextFont), With<ComboText>>,
+) {
+    let Ok((mut text, mut font)) = query.single_mut() else { return };
+
+    if battle_state.combo_count >= 2 {
+        **text = format!("{} HIT COMBO\nx{:.2} DMG",
+            battle_state.combo_count,
+            1.0 + (battle_state.combo_count - 1) as f32 * COMBO_DAMAGE_STEP);
+        font.font_size = if settings.reduce_motion {
+            28.0
+        } else {
+            (24.0 + battle_state.combo_count as f32 * 2.0).min(40.0)
+        };
+    } else {
+        **text = "".to_string();
+    }
+}
+
 pub fn update_room_counter(
     game_progress: Res<GameProgress>,
     mut query: Query<&mut Text, With<RoomCounter>>,
 ) {
     let Ok(mut text) = query.single_mut() else { return };
-    **text = format!("ROOM {} / {}", game_progress.rooms_cleared, game_progress.total_rooms);
+    let assist = if game_progress.assisted { " [ASSIST]" } else { "" };
+    **text = format!("ROOM {} / {}{}", game_progress.rooms_cleared, game_progress.total_rooms, assist);
+}
+
+fn spawn_damage(commands: &mut Commands, text: String, pos: Vec3, color: Color, affinity: Affinity) {
+    let (text, color) = match affinity {
+        Affinity::Normal => (text, color),
+        Affinity::Weak => (format!("WEAK!\n{}", text), Color::srgb(1.0, 0.35, 0.15)),
+        Affinity::Resist => (format!("RESIST\n{}", text), Color::srgb(0.55, 0.6, 0.75)),
+    };
+
+    commands.spawn((
+        Text::new(text),
+        TextFont { font_size: 26.0, ..default() },
+        TextColor(color),
+        Transform::from_translation(pos),
+        DamageNotif {
+            timer: Timer::from_seconds(1.0, TimerMode::Once),
+            velocity: Vec2::new(0.0, 50.0),
+        },
+        BattleSprite,
+    ));
+}
+
+fn spawn_text(commands: &mut Commands, text: &str, pos: Vec3, color: Color) {
+    commands.spawn((
+        Text::new(text),
+        TextFont { font_size: 22.0, ..default() },
+        TextColor(color),
+        Transform::from_translation(pos),
+        DamageNotif {
+            timer: Timer::from_seconds(1.3, TimerMode::Once),
+            velocity: Vec2::new(0.0, 30.0),
+        },
+        BattleSprite,
+    ));
+}
+
+/// Bursts are skipped entirely under reduced flashing.
+fn spawn_particles(commands: &mut Commands, settings: &Settings, pos: Vec3, color: Color, count: usize) {
+    if settings.reduce_flashing {
+        return;
+    }
+    let mut rng = rand::rng();
+    for _ in 0..count {
+        let angle = rng.random_range(0.0..std::f32::consts::TAU);
+        let speed = rng.random_range(60.0..120.0);
+        let vel = Vec2::new(angle.cos() * speed, angle.sin() * speed);
+        
+        commands.spawn((
+            Sprite {
+                color,
+                custom_size: Some(Vec2::new(5.0, 5.0)),
+                ..default()
+            },
+            Transform::from_translation(pos),
+            Particle {
+                timer: Timer::from_seconds(0.6, TimerMode::Once),
+                velocity: vel,
+            },
+            BattleSprite,
+        ));
+    }
 }
@@ -0,0 +1,203 @@
+use bevy::prelude::*;
+use crate::components::*;
+use crate::difficulty::Difficulty;
+use crate::leaderboard::{name_entry_label, NameEntry, NameEntryText};
+use crate::speedrun::SpeedrunTimer;
+use crate::stats::{format_time, RunStats};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EndScreenAction {
+    RetryRoom,
+    ContinueFromCheckpoint,
+    RestartRun,
+    QuitToTitle,
+}
+
+impl EndScreenAction {
+    fn label(&self) -> &'static str {
+        match self {
+            EndScreenAction::RetryRoom => "Retry Room",
+            EndScreenAction::ContinueFromCheckpoint => "Continue from Checkpoint",
+            EndScreenAction::RestartRun => "Restart Run",
+            EndScreenAction::QuitToTitle => "Quit to Title",
+        }
+    }
+}
+
+const GAME_OVER_ITEMS: [EndScreenAction; 4] = [
+    EndScreenAction::RetryRoom,
+    EndScreenAction::ContinueFromCheckpoint,
+    EndScreenAction::RestartRun,
+    EndScreenAction::QuitToTitle,
+];
+const PERMADEATH_ITEMS: [EndScreenAction; 2] = [EndScreenAction::RestartRun, EndScreenAction::QuitToTitle];
+const VICTORY_ITEMS: [EndScreenAction; 2] = [EndScreenAction::RestartRun, EndScreenAction::QuitToTitle];
+
+#[derive(Resource, Default)]
+pub struct EndScreenMenu {
+    pub selected: usize,
+}
+
+#[derive(Component)]
+pub struct EndScreenUI;
+
+#[derive(Component)]
+pub struct EndScreenItem(pub usize);
+
+fn menu_items(state: &GameState, mode: RunMode) -> &'static [EndScreenAction] {
+    match (state, mode) {
+        (GameState::Victory, _) => &VICTORY_ITEMS,
+        (_, RunMode::Permadeath) => &PERMADEATH_ITEMS,
+        _ => &GAME_OVER_ITEMS,
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn spawn_end_screen(
+    mut commands: Commands,
+    state: Res<State<GameState>>,
+    run: Res<RunStats>,
+    game_progress: Res<GameProgress>,
+    mode: Res<RunMode>,
+    difficulty: Res<Difficulty>,
+    name_entry: Res<NameEntry>,
+    speedrun: Res<SpeedrunTimer>,
+    mut menu: ResMut<EndScreenMenu>,
+) {
+    menu.selected = 0;
+    let victory = *state.get() == GameState::Victory;
+
+    let (title, title_color) = if victory {
+        ("★ VICTORY ★", Color::srgb(1.0, 0.9, 0.3))
+    } else {
+        ("GAME OVER", Color::srgb(1.0, 0.3, 0.3))
+    };
+
+    let mut summary = format!(
+        "Mode: {}   Difficulty: {}\nRooms cleared: {} / {}\nTime: {}\nDeaths: {}\nDamage dealt: {}   Damage taken: {}\nBest combo: {}",
+        mode.label(),
+        difficulty.label(),
+        game_progress.rooms_cleared,
+        game_progress.total_rooms,
+        format_time(speedrun.elapsed),
+        run.deaths,
+        run.damage_dealt,
+        run.damage_taken,
+        run.best_combo,
+    );
+    if victory {
+        let loot = if run.loot.is_empty() { "none".to_string() } else { run.loot.join(", ") };
+        summary = format!("{}\nXP earned: {}\nLoot: {}", summary, run.xp, loot);
+    }
+    summary = format!("{}\n\nSCORE: {}", summary, run.score(&difficulty, speedrun.elapsed));
+
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(12.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.85)),
+            GlobalZIndex(8),
+            EndScreenUI,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(title),
+                TextFont { font_size: 48.0, ..default() },
+                TextColor(title_color),
+            ));
+            parent.spawn((
+                Text::new(summary),
+                TextFont { font_size: 22.0, ..default() },
+                TextColor(Color::WHITE),
+                TextLayout::new_with_justify(Justify::Center),
+            ));
+            if victory {
+                parent.spawn((
+                    Text::new(name_entry_label(&name_entry)),
+                    TextFont { font_size: 24.0, ..default() },
+                    TextColor(Color::srgb(0.5, 0.9, 1.0)),
+                    TextLayout::new_with_justify(Justify::Center),
+                    NameEntryText,
+                ));
+            }
+            for (i, action) in menu_items(state.get(), *mode).iter().enumerate() {
+                parent.spawn((
+                    Text::new(action.label()),
+                    TextFont { font_size: 26.0, ..default() },
+                    TextColor(Color::srgb(0.7, 0.7, 0.7)),
+                    EndScreenItem(i),
+                ));
+            }
+        });
+}
+
+pub fn end_screen_input(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    state: Res<State<GameState>>,
+    mode: Res<RunMode>,
+    name_entry: Res<NameEntry>,
+    mut menu: ResMut<EndScreenMenu>,
+    mut commands: Commands,
+    mut game_state: ResMut<NextState<GameState>>,
+) {
+    // The victory menu waits until the score has a name attached
+    if *state.get() == GameState::Victory && !name_entry.submitted {
+        return;
+    }
+
+    let items = menu_items(state.get(), *mode);
+    let count = items.len();
+    if keyboard.just_pressed(KeyCode::KeyW) || keyboard.just_pressed(KeyCode::ArrowUp) {
+        menu.selected = (menu.selected + count - 1) % count;
+    }
+    if keyboard.just_pressed(KeyCode::KeyS) || keyboard.just_pressed(KeyCode::ArrowDown) {
+        menu.selected = (menu.selected + 1) % count;
+    }
+
+    if !(keyboard.just_pressed(KeyCode::Space) || keyboard.just_pressed(KeyCode::Enter)) {
+        return;
+    }
+
+    match items[menu.selected] {
+        EndScreenAction::RetryRoom => {
+            commands.run_system_cached(crate::retry_room);
+            game_state.set(GameState::Overworld);
+        }
+        EndScreenAction::ContinueFromCheckpoint => {
+            commands.run_system_cached(crate::respawn_at_checkpoint);
+            game_state.set(GameState::Overworld);
+        }
+        EndScreenAction::RestartRun => {
+            commands.run_system_cached(crate::reset_run);
+            game_state.set(GameState::Overworld);
+        }
+        EndScreenAction::QuitToTitle => {
+            commands.run_system_cached(crate::reset_run);
+            game_state.set(GameState::MainMenu);
+        }
+    }
+}
+
+pub fn update_end_screen(menu: Res<EndScreenMenu>, mut items: Query<(&EndScreenItem, &mut TextColor)>) {
+    for (item, mut color) in items.iter_mut() {
+        color.0 = if item.0 == menu.selected {
+            Color::srgb(1.0, 0.9, 0.3)
+        } else {
+            Color::srgb(0.7, 0.7, 0.7)
+        };
+    }
+}
+
+pub fn cleanup_end_screen(mut commands: Commands, query: Query<Entity, With<EndScreenUI>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
@@ -0,0 +1,120 @@
+use bevy::prelude::*;
+use crate::ai::BulletPattern;
+use crate::components::*;
+
+/// Scripted path the enemy follows around its arena spot during `BulletHell`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MovementPath {
+    Still,
+    Sweep,
+    FigureEight,
+    Teleport,
+    /// Drops down into the soul's half of the arena and roams it.
+    Prowl,
+}
+
+const TELEPORT_SPOTS: [Vec2; 5] = [
+    Vec2::new(0.0, 0.0),
+    Vec2::new(-110.0, -10.0),
+    Vec2::new(110.0, -10.0),
+    Vec2::new(-60.0, 25.0),
+    Vec2::new(60.0, 25.0),
+];
+
+impl MovementPath {
+    /// Offset from the enemy's home position after `t` seconds on the path.
+    pub fn offset(&self, t: f32) -> Vec2 {
+        match self {
+            MovementPath::Still => Vec2::ZERO,
+            MovementPath::Sweep => Vec2::new((t * 1.2).sin() * 120.0, 0.0),
+            MovementPath::FigureEight => Vec2::new(t.sin() * 100.0, (t * 2.0).sin() * 30.0),
+            MovementPath::Teleport => TELEPORT_SPOTS[(t / 0.9) as usize % TELEPORT_SPOTS.len()],
+            MovementPath::Prowl => Vec2::new((t * 0.9).sin() * 130.0, -90.0 + (t * 1.3).cos() * 90.0),
+        }
+    }
+}
+
+/// Static description of the enemy guarding each room.
+pub struct EnemyDef {
+    pub name: &'static str,
+    /// Health on Normal difficulty.
+    pub health: i32,
+    pub color: Color,
+    pub elements: ElementTable,
+    pub bullet_element: Element,
+    pub patterns: &'static [BulletPattern],
+    pub movement: MovementPath,
+    /// Damage dealt when the soul touches the enemy; 0 means it's harmless to touch.
+    pub contact_damage: i32,
+    /// Item dropped when the enemy is beaten.
+    pub loot: &'static str,
+}
+
+pub const ENEMY_ROSTER: [EnemyDef; TOTAL_ROOMS] = [
+    EnemyDef {
+        name: "Ember Slime",
+        health: 20,
+        color: Color::srgb(1.0, 0.4, 0.4),
+        elements: ElementTable { weak: &[Element::Ice], resist: &[Element::Fire] },
+        bullet_element: Element::Fire,
+        patterns: &[BulletPattern::Wave, BulletPattern::Cross],
+        movement: MovementPath::Sweep,
+        contact_damage: 0,
+        loot: "Ember Core",
+    },
+    EnemyDef {
+        name: "Moss Golem",
+        health: 25,
+        color: Color::srgb(0.4, 1.0, 0.4),
+        elements: ElementTable { weak: &[Element::Fire], resist: &[Element::Shock] },
+        bullet_element: Element::Physical,
+        patterns: &[BulletPattern::Spiral, BulletPattern::Wave],
+        movement: MovementPath::Still,
+        contact_damage: 0,
+        loot: "Moss Tuft",
+    },
+    EnemyDef {
+        name: "Frost Wisp",
+        health: 30,
+        color: Color::srgb(0.4, 0.6, 1.0),
+        elements: ElementTable { weak: &[Element::Fire], resist: &[Element::Ice] },
+        bullet_element: Element::Ice,
+        patterns: &[BulletPattern::Spread, BulletPattern::Spiral, BulletPattern::AimedShot],
+        movement: MovementPath::Teleport,
+        contact_damage: 0,
+        loot: "Frost Shard",
+    },
+    EnemyDef {
+        name: "Volt Beetle",
+        health: 35,
+        color: Color::srgb(1.0, 0.9, 0.3),
+        elements: ElementTable { weak: &[Element::Physical], resist: &[Element::Shock] },
+        bullet_element: Element::Shock,
+        patterns: &[BulletPattern::Cross, BulletPattern::AimedShot, BulletPattern::Spread],
+        movement: MovementPath::Prowl,
+        contact_damage: 3,
+        loot: "Volt Shell",
+    },
+    EnemyDef {
+        name: "Rose Knight",
+        health: 40,
+        color: Color::srgb(1.0, 0.5, 0.8),
+        elements: ElementTable { weak: &[Element::Shock], resist: &[Element::Physical] },
+        bullet_element: Element::Physical,
+        patterns: &[BulletPattern::Wave, BulletPattern::AimedBurst, BulletPattern::Spread],
+        movement: MovementPath::FigureEight,
+        contact_damage: 0,
+        loot: "Rose Crest",
+    },
+    EnemyDef {
+        name: "Storm Warden",
+        health: 45,
+        color: Color::srgb(0.5, 1.0, 0.8),
+        elements: ElementTable { weak: &[Element::Physical], resist: &[Element::Fire, Element::Ice] },
+        bullet_element: Element::Shock,
+        patterns: &[BulletPattern::Spiral, BulletPattern::Cross, BulletPattern::AimedShot, BulletPattern::AimedBurst],
+        movement: MovementPath::Prowl,
+        contact_damage: 4,
+        loot: "Storm Crown",
+    },
+];
@@ -0,0 +1,159 @@
+use bevy::prelude::*;
+use crate::components::*;
+
+/// How well an attack minigame was played.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rating {
+    Perfect,
+    Good,
+    Hit,
+}
+
+impl Rating {
+    pub fn from_accuracy(accuracy: f32) -> Rating {
+        if accuracy >= PERFECT_ACCURACY {
+            Rating::Perfect
+        } else if accuracy >= GOOD_ACCURACY {
+            Rating::Good
+        } else {
+            Rating::Hit
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Rating::Perfect => "★ PERFECT! ★",
+            Rating::Good => "GOOD!",
+            Rating::Hit => "Hit",
+        }
+    }
+
+    pub fn color(&self) -> Color {
+        match self {
+            Rating::Perfect => Color::srgb(1.0, 1.0, 0.3),
+            Rating::Good => Color::srgb(0.3, 1.0, 0.3),
+            Rating::Hit => Color::srgb(0.7, 0.7, 0.7),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DamageSource {
+    Strike,
+    Bullet,
+    Contact,
+    Reflect,
+    Status,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BattleOutcome {
+    Won,
+    Lost,
+    Fled,
+    /// Let go without a finishing blow; the room still counts as cleared.
+    Spared,
+}
+
+/// Emitted once per attack minigame, before the matching `EnemyDamaged`.
+#[derive(Message, Debug, Clone, Copy)]
+pub struct AttackRated {
+    pub rating: Rating,
+    pub combo: usize,
+}
+
+/// Strikes carry their rating, combo and graze power; other sources leave them empty.
+#[derive(Message, Debug, Clone, Copy)]
+pub struct EnemyDamaged {
+    pub amount: i32,
+    pub affinity: Affinity,
+    pub source: DamageSource,
+    pub position: Vec3,
+    pub rating: Option<Rating>,
+    pub combo: usize,
+    pub powered: bool,
+}
+
+#[derive(Message, Debug, Clone, Copy)]
+pub struct PlayerDamaged {
+    pub amount: i32,
+    pub affinity: Affinity,
+    pub source: DamageSource,
+    pub position: Vec3,
+}
+
+/// Callouts during a fight that don't come with damage.
+#[derive(Message, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BattleNotice {
+    EnemyStunned,
+    PlayerStunned,
+    Defending,
+    CaughtOffGuard,
+    CantEscape,
+    FleeFailed,
+    NotReadyToYield,
+}
+
+/// A bullet hit the shield, either as a plain block or inside the parry window.
+#[derive(Message, Debug, Clone, Copy)]
+pub struct BulletDeflected {
+    pub parried: bool,
+    pub position: Vec3,
+}
+
+#[derive(Message, Debug, Clone, Copy)]
+pub struct BulletGrazed {
+    pub position: Vec3,
+}
+
+#[derive(Message, Debug, Clone, Copy)]
+pub struct BattleStarted {
+    pub enemy: Entity,
+    pub boss: bool,
+}
+
+#[derive(Message, Debug, Clone, Copy)]
+pub struct BattleEnded {
+    pub enemy: Entity,
+    pub outcome: BattleOutcome,
+}
+
+#[derive(Message, Debug, Clone, Copy)]
+pub struct CheckpointReached {
+    pub index: usize,
+}
+
+#[derive(Message, Debug, Clone, Copy)]
+pub struct RoomCleared {
+    pub room: usize,
+    pub all_cleared: bool,
+}
+
+/// The player walked out of the exit door with every room cleared.
+#[derive(Message, Debug, Clone, Copy)]
+pub struct GauntletCompleted;
+
+/// Writes battle and room milestones to the log.
+pub fn log_gameplay_events(
+    mut started: MessageReader<BattleStarted>,
+    mut ended: MessageReader<BattleEnded>,
+    mut cleared: MessageReader<RoomCleared>,
+    mut checkpoints: MessageReader<CheckpointReached>,
+    mut completed: MessageReader<GauntletCompleted>,
+) {
+    for event in started.read() {
+        info!("Battle started against {:?} (boss: {})", event.enemy, event.boss);
+    }
+    for event in ended.read() {
+        info!("Battle against {:?} ended: {:?}", event.enemy, event.outcome);
+    }
+    for event in cleared.read() {
+        info!("Room {} cleared (all rooms cleared: {})", event.room, event.all_cleared);
+    }
+    for event in checkpoints.read() {
+        info!("Checkpoint {} reached", event.index);
+    }
+    for _ in completed.read() {
+        info!("Gauntlet completed");
+    }
+}
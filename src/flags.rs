@@ -0,0 +1,113 @@
+use std::collections::HashMap;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::components::*;
+use crate::events::{BattleEnded, BattleOutcome, GauntletCompleted};
+
+const FLAGS_PATH: &str = "flags.ron";
+/// Flags under this prefix belong to the current run and are cleared with it.
+pub const RUN_PREFIX: &str = "run:";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum FlagValue {
+    Bool(bool),
+    Counter(i32),
+    Text(String),
+}
+
+/// Story state: what has been said, found, beaten and finished. Flags named with
+/// `RUN_PREFIX` last until the run is reset; all others persist between sessions.
+/// Reading a flag as the wrong type gives that type's default rather than an error.
+#[derive(Resource, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GameFlags {
+    pub values: HashMap<String, FlagValue>,
+}
+
+impl GameFlags {
+    /// A run left unfinished is not resumed, so its flags are dropped.
+    pub fn load() -> Self {
+        let mut flags: Self = crate::persist::load_ron(FLAGS_PATH);
+        flags.clear_run();
+        flags
+    }
+
+    pub fn save(&self) {
+        crate::persist::save_ron(FLAGS_PATH, self);
+    }
+
+    pub fn is_set(&self, name: &str) -> bool {
+        matches!(self.values.get(name), Some(FlagValue::Bool(true)))
+    }
+
+    pub fn counter(&self, name: &str) -> i32 {
+        match self.values.get(name) {
+            Some(FlagValue::Counter(value)) => *value,
+            _ => 0,
+        }
+    }
+
+    pub fn text(&self, name: &str) -> Option<&str> {
+        match self.values.get(name) {
+            Some(FlagValue::Text(value)) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn set_bool(&mut self, name: &str, value: bool) {
+        self.values.insert(name.to_string(), FlagValue::Bool(value));
+    }
+
+    pub fn add_counter(&mut self, name: &str, amount: i32) {
+        let value = self.counter(name) + amount;
+        self.values.insert(name.to_string(), FlagValue::Counter(value));
+    }
+
+    pub fn set_text(&mut self, name: &str, value: &str) {
+        self.values.insert(name.to_string(), FlagValue::Text(value.to_string()));
+    }
+
+    pub fn clear(&mut self, name: &str) {
+        self.values.remove(name);
+    }
+
+    pub fn clear_run(&mut self) {
+        self.values.retain(|name, _| !name.starts_with(RUN_PREFIX));
+    }
+}
+
+pub fn save_flags(flags: Res<GameFlags>) {
+    flags.save();
+}
+
+/// Writes battle results and the gauntlet's end into the flag store for dialogue and quests to read.
+/// Who was beaten or spared is per run; the tallies persist.
+pub fn record_progress_flags(
+    mut ended: MessageReader<BattleEnded>,
+    mut completed: MessageReader<GauntletCompleted>,
+    enemy_query: Query<&Enemy>,
+    mut flags: ResMut<GameFlags>,
+) {
+    for event in ended.read() {
+        let Ok(enemy) = enemy_query.get(event.enemy) else { continue };
+        flags.set_text("run:last_opponent", enemy.name);
+        match event.outcome {
+            BattleOutcome::Won => {
+                flags.set_bool(&format!("run:defeated:{}", enemy.name), true);
+                flags.add_counter("battles_won", 1);
+            }
+            BattleOutcome::Fled => flags.add_counter("battles_fled", 1),
+            BattleOutcome::Spared => {
+                flags.set_bool(&format!("run:spared:{}", enemy.name), true);
+                flags.add_counter("battles_spared", 1);
+            }
+            BattleOutcome::Lost => flags.add_counter("battles_lost", 1),
+        }
+    }
+
+    for _ in completed.read() {
+        flags.set_bool("run:gauntlet_completed", true);
+        flags.set_bool("gauntlet_completed", true);
+        flags.add_counter("gauntlet_clears", 1);
+    }
+}
@@ -0,0 +1,242 @@
+use bevy::input::keyboard::KeyboardInput;
+use bevy::input::ButtonState;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::ai::EnemyAi;
+use crate::components::*;
+use crate::difficulty::{Difficulty, DifficultyPreset};
+use crate::speedrun::SpeedrunTimer;
+use crate::stats::{format_time, RunStats};
+
+const LEADERBOARD_PATH: &str = "leaderboard.ron";
+const MAX_NAME_LEN: usize = 10;
+const SHOWN_ENTRIES: usize = 10;
+const PRESETS: [DifficultyPreset; 5] = [
+    DifficultyPreset::Easy,
+    DifficultyPreset::Normal,
+    DifficultyPreset::Hard,
+    DifficultyPreset::Nightmare,
+    DifficultyPreset::Custom,
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LeaderboardEntry {
+    pub name: String,
+    pub score: u32,
+    pub seed: u64,
+    pub difficulty: DifficultyPreset,
+    /// The exact multipliers played, so Custom runs can be told apart.
+    #[serde(default)]
+    pub tuning: Difficulty,
+    pub time: f32,
+    /// Set when assist mode was used at any point in the run.
+    pub assisted: bool,
+}
+
+#[derive(Resource, Debug, Default, Serialize, Deserialize)]
+pub struct Leaderboard {
+    pub entries: Vec<LeaderboardEntry>,
+}
+
+impl Leaderboard {
+    pub fn load() -> Self {
+        crate::persist::load_ron(LEADERBOARD_PATH)
+    }
+
+    pub fn save(&self) {
+        crate::persist::save_ron(LEADERBOARD_PATH, self);
+    }
+
+    pub fn insert(&mut self, entry: LeaderboardEntry) {
+        let at = self.entries.partition_point(|existing| existing.score >= entry.score);
+        self.entries.insert(at, entry);
+    }
+
+    /// Best-first entries for one difficulty, optionally limited to a single seed.
+    pub fn top(&self, difficulty: DifficultyPreset, seed: Option<u64>) -> impl Iterator<Item = &LeaderboardEntry> {
+        self.entries
+            .iter()
+            .filter(move |entry| entry.difficulty == difficulty && seed.is_none_or(|seed| entry.seed == seed))
+            .take(SHOWN_ENTRIES)
+    }
+}
+
+/// The name being typed on the victory screen.
+#[derive(Resource, Default)]
+pub struct NameEntry {
+    pub name: String,
+    pub submitted: bool,
+}
+
+#[derive(Component)]
+pub struct NameEntryText;
+
+#[derive(Resource)]
+pub struct LeaderboardView {
+    pub difficulty: usize,
+    pub current_seed_only: bool,
+}
+
+impl Default for LeaderboardView {
+    fn default() -> Self {
+        Self { difficulty: 1, current_seed_only: false }
+    }
+}
+
+#[derive(Component)]
+pub struct LeaderboardUI;
+
+#[derive(Component)]
+pub struct LeaderboardText;
+
+pub fn name_entry_label(entry: &NameEntry) -> String {
+    if entry.submitted {
+        format!("Score saved as {}", entry.name)
+    } else {
+        format!("Enter name: {}_\n[ENTER] Save score", entry.name)
+    }
+}
+
+pub fn reset_name_entry(mut entry: ResMut<NameEntry>) {
+    *entry = NameEntry::default();
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn name_entry_input(
+    state: Res<State<GameState>>,
+    mut key_events: MessageReader<KeyboardInput>,
+    mut entry: ResMut<NameEntry>,
+    mut leaderboard: ResMut<Leaderboard>,
+    run: Res<RunStats>,
+    speedrun: Res<SpeedrunTimer>,
+    difficulty: Res<Difficulty>,
+    ai: Res<EnemyAi>,
+    game_progress: Res<GameProgress>,
+    mut text_query: Query<&mut Text, With<NameEntryText>>,
+) {
+    // Runs every frame so keys pressed before the victory screen never leak into the name
+    if *state.get() != GameState::Victory || entry.submitted {
+        key_events.clear();
+        return;
+    }
+
+    for event in key_events.read() {
+        if event.state != ButtonState::Pressed {
+            continue;
+        }
+        match event.key_code {
+            KeyCode::Backspace => {
+                entry.name.pop();
+            }
+            KeyCode::Enter | KeyCode::NumpadEnter => {
+                if entry.name.is_empty() {
+                    entry.name = "PLAYER".to_string();
+                }
+                leaderboard.insert(LeaderboardEntry {
+                    name: entry.name.clone(),
+                    score: run.score(&difficulty, speedrun.elapsed),
+                    seed: ai.seed,
+                    difficulty: difficulty.preset,
+                    tuning: *difficulty,
+                    time: speedrun.elapsed,
+                    assisted: game_progress.assisted,
+                });
+                leaderboard.save();
+                entry.submitted = true;
+                break;
+            }
+            _ => {
+                let Some(text) = &event.text else { continue };
+                for c in text.chars().filter(|c| c.is_ascii_alphanumeric()) {
+                    if entry.name.len() < MAX_NAME_LEN {
+                        entry.name.push(c.to_ascii_uppercase());
+                    }
+                }
+            }
+        }
+    }
+
+    if let Ok(mut text) = text_query.single_mut() {
+        **text = name_entry_label(&entry);
+    }
+}
+
+fn leaderboard_text(leaderboard: &Leaderboard, view: &LeaderboardView, seed: u64) -> String {
+    let preset = PRESETS[view.difficulty];
+    let filter = if view.current_seed_only { "this seed only" } else { "all seeds" };
+    let mut lines = format!("Difficulty: {:?}   ({})\nCurrent seed: {}\n\n", preset, filter, seed);
+
+    let mut any = false;
+    for (rank, entry) in leaderboard.top(preset, view.current_seed_only.then_some(seed)).enumerate() {
+        any = true;
+        lines.push_str(&format!(
+            "{:>2}. {:<10} {:>8}   {}{}\n",
+            rank + 1,
+            entry.name,
+            entry.score,
+            format_time(entry.time),
+            if entry.assisted { "  (assist)" } else { "" },
+        ));
+    }
+    if !any {
+        lines.push_str("No scores yet\n");
+    }
+    lines
+}
+
+pub fn open_leaderboard(
+    mut commands: Commands,
+    leaderboard: Res<Leaderboard>,
+    difficulty: Res<Difficulty>,
+    ai: Res<EnemyAi>,
+    mut view: ResMut<LeaderboardView>,
+) {
+    view.difficulty = PRESETS.iter().position(|preset| *preset == difficulty.preset).unwrap_or(1);
+
+    crate::overlay::spawn_overlay(
+        &mut commands,
+        LeaderboardUI,
+        "LEADERBOARD",
+        "[A/D] Difficulty | [F] Toggle seed filter | [ESC] Back",
+        |parent| {
+            parent.spawn((
+                Text::new(leaderboard_text(&leaderboard, &view, ai.seed)),
+                TextFont { font_size: 20.0, ..default() },
+                TextColor(Color::srgb(0.9, 0.9, 0.9)),
+                LeaderboardText,
+            ));
+        },
+    );
+}
+
+pub fn leaderboard_input(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut view: ResMut<LeaderboardView>,
+    mut menu_state: ResMut<NextState<LeaderboardState>>,
+) {
+    if crate::overlay::back_pressed(&keyboard) {
+        menu_state.set(LeaderboardState::Closed);
+        return;
+    }
+
+    if keyboard.just_pressed(KeyCode::KeyA) || keyboard.just_pressed(KeyCode::ArrowLeft) {
+        view.difficulty = (view.difficulty + PRESETS.len() - 1) % PRESETS.len();
+    }
+    if keyboard.just_pressed(KeyCode::KeyD) || keyboard.just_pressed(KeyCode::ArrowRight) {
+        view.difficulty = (view.difficulty + 1) % PRESETS.len();
+    }
+    if keyboard.just_pressed(KeyCode::KeyF) {
+        view.current_seed_only = !view.current_seed_only;
+    }
+}
+
+pub fn update_leaderboard(
+    leaderboard: Res<Leaderboard>,
+    view: Res<LeaderboardView>,
+    ai: Res<EnemyAi>,
+    mut text_query: Query<&mut Text, With<LeaderboardText>>,
+) {
+    if let Ok(mut text) = text_query.single_mut() {
+        **text = leaderboard_text(&leaderboard, &view, ai.seed);
+    }
+}
@@ -1,73 +1,275 @@
 use bevy::prelude::*;
 
+mod accessibility;
+mod achievements;
+mod ai;
+mod audio;
 mod components;
+mod difficulty;
+mod dialogue;
 mod combat;
+mod overlay;
 mod overworld;
 mod effects;
+mod end_screens;
+mod enemies;
+mod events;
+mod flags;
+mod leaderboard;
+mod minigames;
+mod pause;
+mod persist;
+mod quests;
+mod settings;
+mod speedrun;
+mod stats;
+mod transitions;
 
 use components::*;
 
 fn main() {
+    let settings = settings::Settings::load();
+
     App::new()
         .add_plugins(DefaultPlugins.set(WindowPlugin {
-            primary_window: Some(Window {
-                title: "Dungeon Gauntlet".to_string(),
-                resolution: (1280, 720).into(),
-                ..default()
-            }),
+            primary_window: Some(settings.window()),
             ..default()
         }))
+        .insert_resource(settings)
+        .init_resource::<settings::SettingsMenu>()
         .init_state::<GameState>()
+        .add_sub_state::<PauseState>()
+        .add_sub_state::<BattleTransition>()
+        .init_state::<SettingsMenuState>()
+        .init_state::<DifficultyMenuState>()
+        .init_state::<LeaderboardState>()
+        .init_state::<AchievementsMenuState>()
+        .insert_resource(achievements::Achievements::load())
+        .init_resource::<achievements::AchievementTracker>()
+        .init_resource::<speedrun::SpeedrunTimer>()
+        .init_state::<DialogueState>()
+        .insert_resource(dialogue::Dialogues::load())
+        .init_resource::<dialogue::ActiveDialogue>()
+        .init_resource::<dialogue::BanterTracker>()
+        .insert_resource(flags::GameFlags::load())
+        .insert_resource(quests::Quests::load())
+        .init_resource::<quests::QuestTracker>()
+        .init_state::<QuestLogState>()
+        .insert_resource(speedrun::PersonalBests::load())
+        .insert_resource(leaderboard::Leaderboard::load())
+        .init_resource::<leaderboard::LeaderboardView>()
+        .init_resource::<leaderboard::NameEntry>()
+        .insert_resource(difficulty::Difficulty::load())
+        .init_resource::<difficulty::DifficultyMenu>()
         .insert_resource(CurrentBattle {
             enemy_entity: Entity::PLACEHOLDER,
             phase: BattlePhase::Intro,
             phase_timer: Timer::from_seconds(0.8, TimerMode::Once),
             player_defended: false,
             combo_count: 0,
+            strikes_this_turn: 0,
+            graze_meter: 0.0,
+            graze_count: 0,
+            initiative: Initiative::Player,
+            rounds_survived: 0,
         })
         .insert_resource(GameProgress {
             current_room: 0,
             rooms_cleared: 0,
             total_rooms: TOTAL_ROOMS,
+            assisted: false,
+        })
+        .insert_resource(Equipment {
+            weapon: Weapon::Sword,
+            armor: Armor::Leather,
         })
+        .init_resource::<minigames::ActiveMinigame>()
+        .insert_resource(ai::EnemyAi::new(rand::random()))
+        .init_resource::<pause::PauseMenu>()
+        .init_resource::<transitions::BattleTransitionFx>()
+        .init_resource::<stats::BattleStats>()
+        .init_resource::<stats::RunStats>()
+        .init_resource::<Inventory>()
+        .init_resource::<RunMode>()
+        .init_resource::<RespawnPoint>()
+        .add_message::<events::CheckpointReached>()
+        .init_resource::<end_screens::EndScreenMenu>()
+        .insert_resource(audio::AudioConfig::load())
+        .init_resource::<audio::MusicState>()
+        .add_message::<audio::PlaySfx>()
+        .add_message::<events::AttackRated>()
+        .add_message::<events::EnemyDamaged>()
+        .add_message::<events::BattleNotice>()
+        .add_message::<events::PlayerDamaged>()
+        .add_message::<events::BulletDeflected>()
+        .add_message::<events::BulletGrazed>()
+        .add_message::<events::BattleStarted>()
+        .add_message::<events::BattleEnded>()
+        .add_message::<events::RoomCleared>()
+        .add_message::<events::GauntletCompleted>()
         .insert_resource(BulletSpawner {
             timer: Timer::from_seconds(0.5, TimerMode::Repeating),
         })
-        .add_systems(Startup, (setup_camera, setup_world, setup_ui))
+        .add_systems(Startup, (setup_camera, setup_world, setup_ui, dialogue::spawn_npcs))
         .add_systems(OnEnter(GameState::MainMenu), setup_main_menu)
-        .add_systems(Update, main_menu_input.run_if(in_state(GameState::MainMenu)))
-        .add_systems(OnExit(GameState::MainMenu), cleanup_main_menu)
+        .add_systems(
+            Update,
+            (
+                main_menu_input.run_if(
+                    in_state(SettingsMenuState::Closed)
+                        .and(in_state(DifficultyMenuState::Closed))
+                        .and(in_state(LeaderboardState::Closed))
+                        .and(in_state(AchievementsMenuState::Closed))
+                        .and(in_state(QuestLogState::Closed)),
+                ),
+                update_main_menu,
+            )
+                .run_if(in_state(GameState::MainMenu)),
+        )
+        .add_systems(OnExit(GameState::MainMenu), (cleanup_main_menu, difficulty::apply_enemy_health))
+        .add_systems(OnEnter(DifficultyMenuState::Open), difficulty::open_difficulty_menu)
+        .add_systems(
+            Update,
+            (difficulty::difficulty_menu_input, difficulty::update_difficulty_menu)
+                .run_if(in_state(DifficultyMenuState::Open)),
+        )
+        .add_systems(OnExit(DifficultyMenuState::Open), difficulty::close_difficulty_menu)
+        .add_systems(OnEnter(LeaderboardState::Open), leaderboard::open_leaderboard)
+        .add_systems(
+            Update,
+            (leaderboard::leaderboard_input, leaderboard::update_leaderboard)
+                .chain()
+                .run_if(in_state(LeaderboardState::Open)),
+        )
+        .add_systems(OnExit(LeaderboardState::Open), overlay::despawn_overlay::<leaderboard::LeaderboardUI>)
+        .add_systems(OnEnter(AchievementsMenuState::Open), achievements::open_achievements_menu)
+        .add_systems(
+            Update,
+            overlay::close_on_back::<AchievementsMenuState>.run_if(in_state(AchievementsMenuState::Open)),
+        )
+        .add_systems(OnExit(AchievementsMenuState::Open), overlay::despawn_overlay::<achievements::AchievementsMenuUI>)
+        .add_systems(
+            Update,
+            (
+                achievements::track_achievements,
+                achievements::spawn_achievement_toasts,
+                achievements::update_achievement_toasts,
+            )
+                .chain(),
+        )
+        .add_systems(Update, (speedrun::record_splits, speedrun::update_speedrun_text).chain())
         .add_systems(
             Update,
             (
                 overworld::player_movement,
+                overworld::cycle_equipment,
+                overworld::patrol_enemies,
                 overworld::check_room_transition,
+                overworld::check_checkpoints,
                 overworld::check_exit_door,
                 overworld::camera_follow,
+                dialogue::update_talk_prompts,
+                dialogue::talk_to_npc,
             )
-                .run_if(in_state(GameState::Overworld)),
+                .run_if(
+                    in_state(GameState::Overworld)
+                        .and(in_state(PauseState::Running))
+                        .and(in_state(DialogueState::Closed)),
+                ),
         )
         .add_systems(
             OnEnter(GameState::Battle),
-            (combat::setup_battle, combat::freeze_camera, hide_overworld_ui),
+            (hide_overworld_ui, ai::reset_enemy_ai, stats::reset_battle_stats, dialogue::reset_banter),
+        )
+        .add_systems(OnEnter(BattleTransition::Entering), transitions::start_enter)
+        .add_systems(
+            Update,
+            transitions::animate_enter
+                .after(effects::update_screen_shake)
+                .run_if(in_state(BattleTransition::Entering).and(in_state(PauseState::Running))),
         )
         .add_systems(
             Update,
             (
                 combat::battle_phase_system,
-                combat::update_attack_indicator,
+                combat::tick_status_timers,
                 combat::player_turn_input,
+                combat::flee_input,
+                combat::spare_input,
                 combat::bullet_hell_player_movement,
+                combat::update_shield,
                 combat::update_telegraph,
+                combat::move_enemy_sprite,
+                combat::check_enemy_contact,
+                ai::plan_enemy_attack.before(combat::spawn_bullet_patterns),
                 combat::spawn_bullet_patterns,
                 combat::update_bullets,
                 combat::check_bullet_collision,
+                combat::check_reflected_bullets,
+                transitions::begin_exit,
+                dialogue::mid_battle_banter,
+            )
+                .run_if(
+                    in_state(BattleTransition::Active)
+                        .and(in_state(PauseState::Running))
+                        .and(in_state(DialogueState::Closed)),
+                ),
+        )
+        .add_systems(OnEnter(BattleTransition::Active), dialogue::pre_battle_banter)
+        .add_systems(
+            Update,
+            (
+                flags::record_progress_flags,
+                quests::track_quest_battles,
+                quests::update_quests.run_if(in_state(GameState::Overworld).or(in_state(GameState::Battle))),
+                flags::save_flags.run_if(resource_changed::<flags::GameFlags>),
             )
+                .chain(),
+        )
+        .add_systems(OnEnter(QuestLogState::Open), quests::open_quest_log)
+        .add_systems(Update, overlay::close_on_back::<QuestLogState>.run_if(in_state(QuestLogState::Open)))
+        .add_systems(OnExit(QuestLogState::Open), overlay::despawn_overlay::<quests::QuestLogUI>)
+        .add_systems(OnEnter(DialogueState::Open), dialogue::open_dialogue_box)
+        .add_systems(
+            Update,
+            (
+                dialogue::dialogue_input.run_if(in_state(PauseState::Running)),
+                dialogue::update_dialogue_box,
+            )
+                .chain()
+                .run_if(in_state(DialogueState::Open)),
+        )
+        .add_systems(OnExit(DialogueState::Open), dialogue::close_dialogue_box)
+        .add_systems(
+            Update,
+            // Results must be graded before the exit card reads them
+            (stats::track_battle_stats, stats::finish_battle)
+                .chain()
+                .after(combat::battle_phase_system)
+                .after(combat::flee_input)
+                .after(combat::spare_input)
+                .before(transitions::begin_exit)
                 .run_if(in_state(GameState::Battle)),
         )
+        .add_systems(
+            Update,
+            speedrun::tick_speedrun
+                .run_if(in_state(GameState::Overworld).or(in_state(GameState::Battle))),
+        )
+        .add_systems(OnEnter(BattleTransition::Exiting), transitions::start_exit)
+        .add_systems(
+            Update,
+            transitions::animate_exit
+                .run_if(in_state(BattleTransition::Exiting).and(in_state(PauseState::Running))),
+        )
         .add_systems(
             OnExit(GameState::Battle),
-            (combat::cleanup_battle, combat::unfreeze_camera, show_overworld_ui),
+            (
+                combat::cleanup_battle,
+                combat::unfreeze_camera,
+                show_overworld_ui,
+                transitions::reset_camera_zoom,
+            ),
         )
         .add_systems(
             Update,
@@ -76,12 +278,76 @@ fn main() {
                 effects::update_screen_shake,
                 effects::update_battle_ui,
                 effects::update_phase_text,
+                effects::update_combo_text,
                 effects::update_particles,
                 effects::update_room_counter,
+                effects::on_enemy_damaged,
+                effects::on_player_damaged,
+                effects::on_bullet_deflected,
+                effects::on_bullet_grazed,
+                effects::on_battle_notice,
+                events::log_gameplay_events,
             ),
         )
-        .add_systems(Update, game_over_screen.run_if(in_state(GameState::GameOver)))
-        .add_systems(Update, victory_screen.run_if(in_state(GameState::Victory)))
+        .add_systems(Update, pause::toggle_pause.run_if(in_state(PauseState::Running)))
+        .add_systems(OnEnter(PauseState::Paused), pause::enter_pause)
+        .add_systems(
+            Update,
+            (
+                pause::pause_menu_input
+                    .run_if(in_state(SettingsMenuState::Closed).and(in_state(QuestLogState::Closed))),
+                pause::update_pause_menu,
+            )
+                .run_if(in_state(PauseState::Paused)),
+        )
+        .add_systems(OnExit(PauseState::Paused), pause::exit_pause)
+        .add_systems(OnEnter(SettingsMenuState::Open), settings::open_settings)
+        .add_systems(
+            Update,
+            (settings::settings_menu_input, settings::update_settings_menu)
+                .run_if(in_state(SettingsMenuState::Open)),
+        )
+        .add_systems(OnExit(SettingsMenuState::Open), settings::close_settings)
+        .add_systems(
+            Update,
+            (settings::apply_settings, accessibility::recolor_enemies)
+                .run_if(resource_changed::<settings::Settings>),
+        )
+        .add_systems(
+            Update,
+            (
+                accessibility::style_new_bullets,
+                accessibility::apply_assist_health,
+                accessibility::mark_assisted_run,
+            )
+                .run_if(in_state(GameState::Overworld).or(in_state(GameState::Battle))),
+        )
+        .add_systems(Update, audio::select_music.run_if(state_changed::<GameState>))
+        .add_systems(
+            Update,
+            (
+                audio::crossfade_music,
+                (audio::telegraph_warning, audio::gameplay_sfx),
+                audio::play_sfx,
+            )
+                .chain(),
+        )
+        .add_systems(OnEnter(GameState::GameOver), end_screens::spawn_end_screen)
+        .add_systems(
+            OnEnter(GameState::Victory),
+            (leaderboard::reset_name_entry, end_screens::spawn_end_screen).chain(),
+        )
+        .add_systems(
+            Update,
+            (end_screens::end_screen_input, end_screens::update_end_screen)
+                .run_if(in_state(GameState::GameOver).or(in_state(GameState::Victory))),
+        )
+        .add_systems(
+            Update,
+            leaderboard::name_entry_input.after(end_screens::end_screen_input),
+        )
+        .add_systems(OnExit(GameState::GameOver), end_screens::cleanup_end_screen)
+        .add_systems(OnExit(GameState::Victory), end_screens::cleanup_end_screen)
         .run();
 }
 
@@ -97,23 +363,14 @@ fn setup_world(mut commands: Commands) {
             custom_size: Some(Vec2::new(24.0, 24.0)),
             ..default()
         },
-        Transform::from_translation(Vec3::new(0.0, -220.0, 1.0)),
+        Transform::from_translation(PLAYER_START),
         Player {
             health: PLAYER_MAX_HEALTH,
             max_health: PLAYER_MAX_HEALTH,
         },
     ));
 
-    let enemy_colors = [
-        Color::srgb(1.0, 0.4, 0.4),
-        Color::srgb(0.4, 1.0, 0.4),
-        Color::srgb(0.4, 0.6, 1.0),
-        Color::srgb(1.0, 0.9, 0.3),
-        Color::srgb(1.0, 0.5, 0.8),
-        Color::srgb(0.5, 1.0, 0.8),
-    ];
-
-    for i in 0..TOTAL_ROOMS {
+    for (i, def) in enemies::ENEMY_ROSTER.iter().enumerate() {
         let y_pos = (i as f32 * ROOM_HEIGHT) - 150.0;
 
         // Room background
@@ -133,17 +390,26 @@ fn setup_world(mut commands: Commands) {
         // Enemy
         commands.spawn((
             Sprite {
-                color: enemy_colors[i],
+                color: def.color,
                 custom_size: Some(Vec2::new(32.0, 32.0)),
                 ..default()
             },
             Transform::from_translation(Vec3::new(0.0, y_pos, 0.5)),
             Enemy {
-                health: 20 + (i as i32 * 5),
-                max_health: 20 + (i as i32 * 5),
+                name: def.name,
+                health: def.health,
+                max_health: def.health,
                 room_index: i,
                 attack_pattern: i,
+                boss: i == TOTAL_ROOMS - 1,
+                elements: def.elements,
+                bullet_element: def.bullet_element,
+                patterns: def.patterns,
+                movement: def.movement,
+                contact_damage: def.contact_damage,
             },
+            StatusEffects::default(),
+            Patrol::new(Vec3::new(0.0, y_pos, 0.5)),
         ));
 
         // Room boundaries
@@ -159,6 +425,21 @@ fn setup_world(mut commands: Commands) {
         }
     }
 
+    // Checkpoints sit in the middle of the corridor just past their room
+    for (index, room) in CHECKPOINT_ROOMS.iter().enumerate() {
+        let y_pos = (*room as f32 * ROOM_HEIGHT) - 150.0 + ROOM_HEIGHT / 2.0;
+        commands.spawn((
+            Sprite {
+                color: Color::srgb(0.3, 0.5, 0.4),
+                custom_size: Some(Vec2::new(18.0, 18.0)),
+                ..default()
+            },
+            Transform::from_translation(Vec3::new(0.0, y_pos, 0.4))
+                .with_rotation(Quat::from_rotation_z(std::f32::consts::FRAC_PI_4)),
+            Checkpoint { index, activated: false },
+        ));
+    }
+
     // Exit door
     commands.spawn((
         Sprite {
@@ -186,7 +467,7 @@ fn setup_ui(mut commands: Commands) {
 
     // TOP LEFT - Health text
     commands.spawn((
-        Text::new("♥ Player: 30/30\n◆ Enemy: 20/20"),
+        Text::new("♥ Player: 30/30\n◆ Enemy: 20/20\n✦ Graze: [----------]"),
         TextFont {
             font_size: 18.0,
             ..default()
@@ -239,9 +520,28 @@ fn setup_ui(mut commands: Commands) {
         BattleUI,
     ));
 
+    // RIGHT - Combo counter
+    commands.spawn((
+        Text::new(""),
+        TextFont {
+            font_size: 28.0,
+            ..default()
+        },
+        TextColor(Color::srgb(1.0, 0.6, 0.2)),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(60.0),
+            right: Val::Px(20.0),
+            ..default()
+        },
+        ComboText,
+        Visibility::Hidden,
+        BattleUI,
+    ));
+
     // BOTTOM LEFT - Battle controls
     commands.spawn((
-        Text::new("[SPACE] Attack | [2] Defend | [WASD] Dodge"),
+        Text::new("Attack: follow the prompt | [2] Shield | [3] Flee | [4] Spare | [WASD] Dodge\nShield: [Q/E] Turn | [SPACE] Parry"),
         TextFont {
             font_size: 18.0,
             ..default()
@@ -260,7 +560,7 @@ fn setup_ui(mut commands: Commands) {
 
     // BOTTOM LEFT - Overworld instructions
     commands.spawn((
-        Text::new("WASD: Move | Get close to enemies to battle!"),
+        Text::new("WASD: Move | Q: Weapon (Sword) | R: Armor (Leather) | Get close to enemies to battle!"),
         TextFont {
             font_size: 16.0,
             ..default()
@@ -274,6 +574,26 @@ fn setup_ui(mut commands: Commands) {
         },
         OverworldInstructions,
     ));
+
+    // BOTTOM RIGHT - Speedrun timer
+    commands.spawn((
+        Text::new(""),
+        TextFont {
+            font_size: 18.0,
+            ..default()
+        },
+        TextColor(Color::WHITE),
+        TextLayout::new_with_justify(Justify::Right),
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(20.0),
+            right: Val::Px(20.0),
+            ..default()
+        },
+        GlobalZIndex(9),
+        Visibility::Hidden,
+        speedrun::SpeedrunText,
+    ));
 }
 
 fn hide_overworld_ui(mut query: Query<&mut Visibility, With<OverworldInstructions>>) {
@@ -288,9 +608,23 @@ fn show_overworld_ui(mut query: Query<&mut Visibility, With<OverworldInstruction
     }
 }
 
-fn setup_main_menu(mut commands: Commands) {
+fn main_menu_text(mode: RunMode, difficulty: &difficulty::Difficulty, seed: u64) -> String {
+    format!(
+        "◆ DUNGEON GAUNTLET ◆\n\nPress [SPACE] to Start\n[P] Mode: {}\n[D] Difficulty: {}\n[S] Seed: {}\n[O] Settings\n[L] Leaderboard\n[A] Achievements\n[J] Quest Log\n\nDefeat 6 enemies and reach the exit!",
+        mode.label(),
+        difficulty.label(),
+        seed
+    )
+}
+
+fn setup_main_menu(
+    mut commands: Commands,
+    mode: Res<RunMode>,
+    difficulty: Res<difficulty::Difficulty>,
+    ai: Res<ai::EnemyAi>,
+) {
     commands.spawn((
-        Text::new("◆ DUNGEON GAUNTLET ◆\n\nPress [SPACE] to Start\n\nDefeat 6 enemies and reach the exit!"),
+        Text::new(main_menu_text(*mode, &difficulty, ai.seed)),
         TextFont {
             font_size: 42.0,
             ..default()
@@ -308,12 +642,46 @@ fn setup_main_menu(mut commands: Commands) {
     ));
 }
 
+#[allow(clippy::too_many_arguments)]
 fn main_menu_input(
     keyboard: Res<ButtonInput<KeyCode>>,
     mut game_state: ResMut<NextState<GameState>>,
+    mut settings_state: ResMut<NextState<SettingsMenuState>>,
+    mut difficulty_state: ResMut<NextState<DifficultyMenuState>>,
+    mut leaderboard_state: ResMut<NextState<LeaderboardState>>,
+    mut achievements_state: ResMut<NextState<AchievementsMenuState>>,
+    mut quest_log_state: ResMut<NextState<QuestLogState>>,
+    mut mode: ResMut<RunMode>,
+    mut ai: ResMut<ai::EnemyAi>,
 ) {
     if keyboard.just_pressed(KeyCode::Space) {
         game_state.set(GameState::Overworld);
+    } else if keyboard.just_pressed(KeyCode::KeyO) {
+        settings_state.set(SettingsMenuState::Open);
+    } else if keyboard.just_pressed(KeyCode::KeyD) {
+        difficulty_state.set(DifficultyMenuState::Open);
+    } else if keyboard.just_pressed(KeyCode::KeyL) {
+        leaderboard_state.set(LeaderboardState::Open);
+    } else if keyboard.just_pressed(KeyCode::KeyA) {
+        achievements_state.set(AchievementsMenuState::Open);
+    } else if keyboard.just_pressed(KeyCode::KeyJ) {
+        quest_log_state.set(QuestLogState::Open);
+    } else if keyboard.just_pressed(KeyCode::KeyP) {
+        *mode = mode.toggle();
+    } else if keyboard.just_pressed(KeyCode::KeyS) {
+        // Every fight is reseeded from this, so the same seed replays the same run
+        *ai = ai::EnemyAi::new(rand::random());
+    }
+}
+
+fn update_main_menu(
+    mode: Res<RunMode>,
+    difficulty: Res<difficulty::Difficulty>,
+    ai: Res<ai::EnemyAi>,
+    mut text_query: Query<&mut Text, With<MainMenuUI>>,
+) {
+    if let Ok(mut text) = text_query.single_mut() {
+        **text = main_menu_text(*mode, &difficulty, ai.seed);
     }
 }
 
@@ -323,72 +691,99 @@ fn cleanup_main_menu(mut commands: Commands, query: Query<Entity, With<MainMenuU
     }
 }
 
-fn game_over_screen(
-    keyboard: Res<ButtonInput<KeyCode>>,
-    mut game_state: ResMut<NextState<GameState>>,
-    mut commands: Commands,
-    player_query: Query<Entity, With<Player>>,
-    mut enemy_query: Query<&mut Enemy>,
+/// Puts the player, every enemy and every room back to the start of a run.
+#[allow(clippy::too_many_arguments)]
+fn reset_run(
+    mut player_query: Query<(&mut Transform, &mut Player), Without<PlayerSprite>>,
+    mut enemy_query: Query<(&mut Enemy, &mut Transform, &mut Patrol), Without<Player>>,
     mut rooms_query: Query<&mut Room>,
     mut game_progress: ResMut<GameProgress>,
+    mut run_stats: ResMut<stats::RunStats>,
+    mut inventory: ResMut<Inventory>,
+    mut respawn: ResMut<RespawnPoint>,
+    mut checkpoint_query: Query<(&mut Checkpoint, &mut Sprite)>,
+    mut achievement_tracker: ResMut<achievements::AchievementTracker>,
+    mut speedrun: ResMut<speedrun::SpeedrunTimer>,
+    mut dialogue_state: ResMut<NextState<DialogueState>>,
+    mut flags: ResMut<flags::GameFlags>,
 ) {
-    if keyboard.just_pressed(KeyCode::Space) {
-        game_progress.rooms_cleared = 0;
-        game_progress.current_room = 0;
-
-        if let Ok(player_entity) = player_query.single() {
-            commands.entity(player_entity).insert((
-                Transform::from_translation(Vec3::new(0.0, -220.0, 1.0)),
-                Player {
-                    health: PLAYER_MAX_HEALTH,
-                    max_health: PLAYER_MAX_HEALTH,
-                },
-            ));
-        }
+    game_progress.rooms_cleared = 0;
+    game_progress.current_room = 0;
+    game_progress.assisted = false;
+    *run_stats = stats::RunStats::default();
+    // Run flags gate items, so they go with the inventory
+    *inventory = Inventory::default();
+    flags.clear_run();
+    *respawn = RespawnPoint::default();
+    *achievement_tracker = achievements::AchievementTracker::default();
+    *speedrun = speedrun::SpeedrunTimer::default();
+    dialogue_state.set(DialogueState::Closed);
+
+    for (mut checkpoint, mut sprite) in checkpoint_query.iter_mut() {
+        checkpoint.activated = false;
+        sprite.color = Color::srgb(0.3, 0.5, 0.4);
+    }
 
-        for mut enemy in enemy_query.iter_mut() {
-            enemy.health = enemy.max_health;
-        }
+    if let Ok((mut transform, mut player)) = player_query.single_mut() {
+        transform.translation = PLAYER_START;
+        player.health = PLAYER_MAX_HEALTH;
+        player.max_health = PLAYER_MAX_HEALTH;
+    }
 
-        for mut room in rooms_query.iter_mut() {
-            room.cleared = false;
-        }
+    for (mut enemy, mut transform, mut patrol) in enemy_query.iter_mut() {
+        enemy.health = enemy.max_health;
+        let home = patrol.home;
+        transform.translation = home;
+        *patrol = Patrol::new(home);
+    }
 
-        game_state.set(GameState::Overworld);
+    for mut room in rooms_query.iter_mut() {
+        room.cleared = false;
     }
 }
 
-fn victory_screen(
-    keyboard: Res<ButtonInput<KeyCode>>,
-    mut game_state: ResMut<NextState<GameState>>,
-    mut commands: Commands,
-    player_query: Query<Entity, With<Player>>,
-    mut enemy_query: Query<&mut Enemy>,
-    mut rooms_query: Query<&mut Room>,
-    mut game_progress: ResMut<GameProgress>,
+/// Sends the player back to the last checkpoint they touched. Cleared rooms
+/// stay cleared; every enemy still standing is healed and sent home.
+fn respawn_at_checkpoint(
+    player_query: Query<(&mut Transform, &mut Player), Without<PlayerSprite>>,
+    enemy_query: Query<(&mut Enemy, &mut Transform, &mut Patrol), Without<Player>>,
+    rooms_query: Query<&Room>,
+    respawn: Res<RespawnPoint>,
 ) {
-    if keyboard.just_pressed(KeyCode::Space) {
-        game_progress.rooms_cleared = 0;
-        game_progress.current_room = 0;
-
-        if let Ok(player_entity) = player_query.single() {
-            commands.entity(player_entity).insert((
-                Transform::from_translation(Vec3::new(0.0, -220.0, 1.0)),
-                Player {
-                    health: PLAYER_MAX_HEALTH,
-                    max_health: PLAYER_MAX_HEALTH,
-                },
-            ));
-        }
+    respawn_at(respawn.position, player_query, enemy_query, rooms_query);
+}
 
-        for mut enemy in enemy_query.iter_mut() {
-            enemy.health = enemy.max_health;
-        }
+/// Puts the player back at the doorway of the room they fell in.
+fn retry_room(
+    player_query: Query<(&mut Transform, &mut Player), Without<PlayerSprite>>,
+    enemy_query: Query<(&mut Enemy, &mut Transform, &mut Patrol), Without<Player>>,
+    rooms_query: Query<&Room>,
+    game_progress: Res<GameProgress>,
+) {
+    let room_y = (game_progress.current_room as f32 * ROOM_HEIGHT) - 150.0;
+    let position = Vec3::new(0.0, room_y - ROOM_HEIGHT / 2.0, PLAYER_START.z);
+    respawn_at(position, player_query, enemy_query, rooms_query);
+}
 
-        for mut room in rooms_query.iter_mut() {
-            room.cleared = false;
-        }
+/// Heals the player at `position` and resets every enemy whose room is still uncleared.
+fn respawn_at(
+    position: Vec3,
+    mut player_query: Query<(&mut Transform, &mut Player), Without<PlayerSprite>>,
+    mut enemy_query: Query<(&mut Enemy, &mut Transform, &mut Patrol), Without<Player>>,
+    rooms_query: Query<&Room>,
+) {
+    if let Ok((mut transform, mut player)) = player_query.single_mut() {
+        transform.translation = position;
+        player.health = player.max_health;
+    }
 
-        game_state.set(GameState::Overworld);
+    for (mut enemy, mut transform, mut patrol) in enemy_query.iter_mut() {
+        let room_cleared = rooms_query.iter().any(|room| room.index == enemy.room_index && room.cleared);
+        if !room_cleared {
+            enemy.health = enemy.max_health;
+            let home = patrol.home;
+            transform.translation = home;
+            *patrol = Patrol::new(home);
+        }
     }
 }
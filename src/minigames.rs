@@ -0,0 +1,382 @@
+use bevy::prelude::*;
+use crate::components::*;
+use rand::Rng;
+
+pub const MINIGAME_Y: f32 = BATTLE_ARENA_Y - 110.0;
+
+pub type MinigameSprites<'w, 's> = Query<
+    'w,
+    's,
+    (&'static mut Transform, &'static mut Sprite),
+    (With<MinigameSprite>, Without<EnemySprite>),
+>;
+
+/// An attack input challenge run during `PlayerTurn`.
+///
+/// Implementations spawn their own sprites (tagged `MinigameSprite`) and
+/// report a normalized accuracy in `0.0..=1.0` once the player is done.
+pub trait AttackMinigame: Send + Sync {
+    fn spawn(&mut self, commands: &mut Commands);
+    fn update(
+        &mut self,
+        time: &Time,
+        keyboard: &ButtonInput<KeyCode>,
+        sprites: &mut MinigameSprites,
+    ) -> Option<f32>;
+}
+
+#[derive(Resource, Default)]
+pub struct ActiveMinigame {
+    pub game: Option<Box<dyn AttackMinigame>>,
+}
+
+/// Builds the minigame for a weapon. `strike` is how many hits have already
+/// landed this turn, so combo follow-ups get progressively faster.
+pub fn minigame_for(weapon: Weapon, strike: usize) -> Box<dyn AttackMinigame> {
+    let tempo = 1.0 + strike as f32 * 0.25;
+    match weapon {
+        Weapon::Sword => Box::new(TimingBar::new(220.0 + strike as f32 * 60.0)),
+        Weapon::WarDrum => Box::new(RhythmSequence::new(0.55 / tempo)),
+        Weapon::Hammer => Box::new(ChargeMeter::new(0.9 * tempo)),
+        Weapon::Bow => Box::new(Reticle::new(tempo)),
+        Weapon::TwinBlades => Box::new(MultiBar::new(260.0 * tempo)),
+    }
+}
+
+fn spawn_part(commands: &mut Commands, color: Color, size: Vec2, pos: Vec3) -> Entity {
+    commands
+        .spawn((
+            Sprite {
+                color,
+                custom_size: Some(size),
+                ..default()
+            },
+            Transform::from_translation(pos),
+            MinigameSprite,
+            BattleSprite,
+        ))
+        .id()
+}
+
+fn spawn_hint(commands: &mut Commands, text: &str, y: f32) {
+    commands.spawn((
+        Text2d::new(text),
+        TextFont { font_size: 14.0, ..default() },
+        TextColor(Color::srgb(0.8, 0.8, 0.8)),
+        Transform::from_translation(Vec3::new(0.0, y, 11.6)),
+        MinigameSprite,
+        BattleSprite,
+    ));
+}
+
+/// The original sliding bar: strike while the indicator is over the centre.
+pub struct TimingBar {
+    speed: f32,
+    direction: f32,
+    x: f32,
+    indicator: Entity,
+}
+
+impl TimingBar {
+    pub fn new(speed: f32) -> Self {
+        Self { speed, direction: 1.0, x: -160.0, indicator: Entity::PLACEHOLDER }
+    }
+}
+
+impl AttackMinigame for TimingBar {
+    fn spawn(&mut self, commands: &mut Commands) {
+        spawn_part(commands, Color::srgb(0.25, 0.25, 0.25), Vec2::new(320.0, 24.0), Vec3::new(0.0, MINIGAME_Y, 11.0));
+        spawn_part(commands, Color::srgba(0.3, 1.0, 0.3, 0.4), Vec2::new(50.0, 24.0), Vec3::new(0.0, MINIGAME_Y, 11.1));
+        self.indicator = spawn_part(commands, Color::srgb(1.0, 1.0, 0.3), Vec2::new(10.0, 32.0), Vec3::new(self.x, MINIGAME_Y, 11.5));
+        spawn_hint(commands, "[SPACE] Strike in the green zone", MINIGAME_Y - 26.0);
+    }
+
+    fn update(&mut self, time: &Time, keyboard: &ButtonInput<KeyCode>, sprites: &mut MinigameSprites) -> Option<f32> {
+        self.x += self.speed * self.direction * time.delta_secs();
+
+        if self.x > 160.0 {
+            self.x = 160.0;
+            self.direction = -1.0;
+        } else if self.x < -160.0 {
+            self.x = -160.0;
+            self.direction = 1.0;
+        }
+
+        if let Ok((mut transform, _)) = sprites.get_mut(self.indicator) {
+            transform.translation.x = self.x;
+        }
+
+        if keyboard.just_pressed(KeyCode::Space) {
+            return Some(1.0 - self.x.abs() / 160.0);
+        }
+        None
+    }
+}
+
+const RHYTHM_PROMPTS: usize = 4;
+const RHYTHM_LEAD_IN: f32 = 0.8;
+const RHYTHM_WINDOW: f32 = 0.25;
+const RHYTHM_KEYS: [(KeyCode, &str); 4] = [
+    (KeyCode::ArrowLeft, "←"),
+    (KeyCode::ArrowUp, "↑"),
+    (KeyCode::ArrowRight, "→"),
+    (KeyCode::ArrowDown, "↓"),
+];
+
+/// A short sequence of arrow prompts, each to be pressed on its beat.
+pub struct RhythmSequence {
+    interval: f32,
+    elapsed: f32,
+    prompts: Vec<(KeyCode, Entity)>,
+    scores: Vec<f32>,
+    cursor: Entity,
+}
+
+impl RhythmSequence {
+    pub fn new(interval: f32) -> Self {
+        Self { interval, elapsed: 0.0, prompts: Vec::new(), scores: Vec::new(), cursor: Entity::PLACEHOLDER }
+    }
+
+    fn prompt_x(index: f32) -> f32 {
+        -120.0 + index * 80.0
+    }
+}
+
+impl AttackMinigame for RhythmSequence {
+    fn spawn(&mut self, commands: &mut Commands) {
+        let mut rng = rand::rng();
+        spawn_part(commands, Color::srgb(0.25, 0.25, 0.25), Vec2::new(320.0, 40.0), Vec3::new(0.0, MINIGAME_Y, 11.0));
+
+        for i in 0..RHYTHM_PROMPTS {
+            let (key, glyph) = RHYTHM_KEYS[rng.random_range(0..RHYTHM_KEYS.len())];
+            let entity = spawn_part(commands, Color::srgb(0.5, 0.5, 0.6), Vec2::new(32.0, 32.0),
+                Vec3::new(Self::prompt_x(i as f32), MINIGAME_Y, 11.1));
+            commands.entity(entity).with_children(|parent| {
+                parent.spawn((
+                    Text2d::new(glyph),
+                    TextFont { font_size: 24.0, ..default() },
+                    TextColor(Color::BLACK),
+                    Transform::from_translation(Vec3::new(0.0, 0.0, 0.1)),
+                ));
+            });
+            self.prompts.push((key, entity));
+        }
+
+        self.cursor = spawn_part(commands, Color::srgb(1.0, 1.0, 0.3), Vec2::new(6.0, 44.0),
+            Vec3::new(Self::prompt_x(-RHYTHM_LEAD_IN / self.interval), MINIGAME_Y, 11.5));
+        spawn_hint(commands, "[ARROWS] Press each arrow as the cursor hits it", MINIGAME_Y - 34.0);
+    }
+
+    fn update(&mut self, time: &Time, keyboard: &ButtonInput<KeyCode>, sprites: &mut MinigameSprites) -> Option<f32> {
+        self.elapsed += time.delta_secs();
+
+        if let Ok((mut transform, _)) = sprites.get_mut(self.cursor) {
+            transform.translation.x = Self::prompt_x((self.elapsed - RHYTHM_LEAD_IN) / self.interval);
+        }
+
+        let current = self.scores.len();
+        if current < self.prompts.len() {
+            let (expected, entity) = self.prompts[current];
+            let offset = self.elapsed - (RHYTHM_LEAD_IN + current as f32 * self.interval);
+            let pressed = RHYTHM_KEYS.iter().map(|(key, _)| *key).find(|key| keyboard.just_pressed(*key));
+
+            let score = match pressed {
+                Some(key) if key == expected && offset.abs() <= RHYTHM_WINDOW => {
+                    Some(1.0 - offset.abs() / RHYTHM_WINDOW * 0.6)
+                }
+                Some(_) => Some(0.0),
+                None if offset > RHYTHM_WINDOW => Some(0.0),
+                None => None,
+            };
+
+            if let Some(score) = score {
+                if let Ok((_, mut sprite)) = sprites.get_mut(entity) {
+                    sprite.color = if score >= PERFECT_ACCURACY {
+                        Color::srgb(0.3, 1.0, 0.3)
+                    } else if score > 0.0 {
+                        Color::srgb(1.0, 0.9, 0.3)
+                    } else {
+                        Color::srgb(1.0, 0.3, 0.3)
+                    };
+                }
+                self.scores.push(score);
+            }
+        }
+
+        if self.scores.len() == self.prompts.len() {
+            return Some(self.scores.iter().sum::<f32>() / self.scores.len() as f32);
+        }
+        None
+    }
+}
+
+const CHARGE_SWEET_SPOT: f32 = 0.85;
+
+/// Hold to charge, release as close to the sweet spot as possible.
+/// Holding past full overcharges and whiffs.
+pub struct ChargeMeter {
+    rate: f32,
+    level: f32,
+    charging: bool,
+    fill: Entity,
+}
+
+impl ChargeMeter {
+    pub fn new(rate: f32) -> Self {
+        Self { rate, level: 0.0, charging: false, fill: Entity::PLACEHOLDER }
+    }
+}
+
+impl AttackMinigame for ChargeMeter {
+    fn spawn(&mut self, commands: &mut Commands) {
+        spawn_part(commands, Color::srgb(0.25, 0.25, 0.25), Vec2::new(320.0, 24.0), Vec3::new(0.0, MINIGAME_Y, 11.0));
+        spawn_part(commands, Color::srgba(0.3, 1.0, 0.3, 0.4), Vec2::new(32.0, 24.0),
+            Vec3::new(-160.0 + CHARGE_SWEET_SPOT * 320.0, MINIGAME_Y, 11.2));
+        self.fill = spawn_part(commands, Color::srgb(1.0, 0.6, 0.2), Vec2::new(0.0, 18.0), Vec3::new(-160.0, MINIGAME_Y, 11.1));
+        spawn_hint(commands, "[HOLD SPACE] Charge, release in the green zone", MINIGAME_Y - 26.0);
+    }
+
+    fn update(&mut self, time: &Time, keyboard: &ButtonInput<KeyCode>, sprites: &mut MinigameSprites) -> Option<f32> {
+        if !self.charging {
+            self.charging = keyboard.just_pressed(KeyCode::Space);
+            return None;
+        }
+
+        self.level += self.rate * time.delta_secs();
+
+        if let Ok((mut transform, mut sprite)) = sprites.get_mut(self.fill) {
+            let width = self.level.min(1.0) * 320.0;
+            sprite.custom_size = Some(Vec2::new(width, 18.0));
+            transform.translation.x = -160.0 + width / 2.0;
+        }
+
+        if self.level >= 1.0 {
+            return Some(0.0);
+        }
+        if keyboard.just_released(KeyCode::Space) {
+            return Some((1.0 - (self.level - CHARGE_SWEET_SPOT).abs() * 4.0).clamp(0.0, 1.0));
+        }
+        None
+    }
+}
+
+/// A reticle drifts around a target; fire when it is centred.
+pub struct Reticle {
+    speed: f32,
+    elapsed: f32,
+    reticle: Entity,
+}
+
+impl Reticle {
+    pub fn new(speed: f32) -> Self {
+        Self { speed, elapsed: 0.0, reticle: Entity::PLACEHOLDER }
+    }
+
+    fn offset(&self) -> Vec2 {
+        let t = self.elapsed * self.speed;
+        Vec2::new((t * 1.7).sin() * 130.0, (t * 2.9).sin() * 22.0)
+    }
+}
+
+impl AttackMinigame for Reticle {
+    fn spawn(&mut self, commands: &mut Commands) {
+        spawn_part(commands, Color::srgb(0.25, 0.25, 0.25), Vec2::new(320.0, 60.0), Vec3::new(0.0, MINIGAME_Y, 11.0));
+        spawn_part(commands, Color::srgba(1.0, 0.3, 0.3, 0.5), Vec2::new(40.0, 40.0), Vec3::new(0.0, MINIGAME_Y, 11.1));
+        spawn_part(commands, Color::srgb(1.0, 0.3, 0.3), Vec2::new(12.0, 12.0), Vec3::new(0.0, MINIGAME_Y, 11.2));
+        self.reticle = spawn_part(commands, Color::srgba(1.0, 1.0, 0.3, 0.8), Vec2::new(16.0, 16.0), Vec3::new(0.0, MINIGAME_Y, 11.5));
+        spawn_hint(commands, "[SPACE] Fire when the reticle is on target", MINIGAME_Y - 44.0);
+    }
+
+    fn update(&mut self, time: &Time, keyboard: &ButtonInput<KeyCode>, sprites: &mut MinigameSprites) -> Option<f32> {
+        self.elapsed += time.delta_secs();
+        let offset = self.offset();
+
+        if let Ok((mut transform, _)) = sprites.get_mut(self.reticle) {
+            transform.translation.x = offset.x;
+            transform.translation.y = MINIGAME_Y + offset.y;
+        }
+
+        if keyboard.just_pressed(KeyCode::Space) {
+            return Some((1.0 - offset.length() / 100.0).clamp(0.0, 1.0));
+        }
+        None
+    }
+}
+
+struct SweepBar {
+    delay: f32,
+    x: f32,
+    indicator: Entity,
+    score: Option<f32>,
+}
+
+/// Several indicators sweep across stacked bars one after another; press
+/// once for each as it crosses the centre.
+pub struct MultiBar {
+    speed: f32,
+    elapsed: f32,
+    bars: Vec<SweepBar>,
+}
+
+impl MultiBar {
+    pub fn new(speed: f32) -> Self {
+        Self { speed, elapsed: 0.0, bars: Vec::new() }
+    }
+}
+
+impl AttackMinigame for MultiBar {
+    fn spawn(&mut self, commands: &mut Commands) {
+        for i in 0..3 {
+            let y = MINIGAME_Y + 24.0 - i as f32 * 24.0;
+            spawn_part(commands, Color::srgb(0.25, 0.25, 0.25), Vec2::new(320.0, 16.0), Vec3::new(0.0, y, 11.0));
+            spawn_part(commands, Color::srgba(0.3, 1.0, 0.3, 0.4), Vec2::new(40.0, 16.0), Vec3::new(0.0, y, 11.1));
+            let indicator = spawn_part(commands, Color::srgb(1.0, 1.0, 0.3), Vec2::new(8.0, 20.0), Vec3::new(-160.0, y, 11.5));
+            self.bars.push(SweepBar { delay: i as f32 * 0.4, x: -160.0, indicator, score: None });
+        }
+        spawn_hint(commands, "[SPACE] Press as each marker crosses the centre", MINIGAME_Y - 46.0);
+    }
+
+    fn update(&mut self, time: &Time, keyboard: &ButtonInput<KeyCode>, sprites: &mut MinigameSprites) -> Option<f32> {
+        self.elapsed += time.delta_secs();
+
+        for bar in self.bars.iter_mut().filter(|bar| bar.score.is_none()) {
+            bar.x = -160.0 + (self.elapsed - bar.delay).max(0.0) * self.speed;
+            if bar.x > 160.0 {
+                bar.score = Some(0.0);
+                if let Ok((_, mut sprite)) = sprites.get_mut(bar.indicator) {
+                    sprite.color = Color::srgb(1.0, 0.3, 0.3);
+                }
+            }
+            if let Ok((mut transform, _)) = sprites.get_mut(bar.indicator) {
+                transform.translation.x = bar.x.min(160.0);
+            }
+        }
+
+        if keyboard.just_pressed(KeyCode::Space) {
+            let elapsed = self.elapsed;
+            let closest = self
+                .bars
+                .iter_mut()
+                .filter(|bar| bar.score.is_none() && elapsed >= bar.delay)
+                .min_by(|a, b| a.x.abs().total_cmp(&b.x.abs()));
+
+            if let Some(bar) = closest {
+                let score = 1.0 - bar.x.abs() / 160.0;
+                bar.score = Some(score);
+                if let Ok((_, mut sprite)) = sprites.get_mut(bar.indicator) {
+                    sprite.color = if score >= PERFECT_ACCURACY {
+                        Color::srgb(0.3, 1.0, 0.3)
+                    } else {
+                        Color::srgb(0.7, 0.7, 0.7)
+                    };
+                }
+            }
+        }
+
+        if self.bars.iter().all(|bar| bar.score.is_some()) {
+            let total: f32 = self.bars.iter().filter_map(|bar| bar.score).sum();
+            return Some(total / self.bars.len() as f32);
+        }
+        None
+    }
+}
@@ -0,0 +1,63 @@
+use bevy::prelude::*;
+use bevy::state::state::FreelyMutableState;
+
+/// Spawns a full-screen menu over whatever is running: a title, the rows `body`
+/// adds, then a key hint. Everything is a child of one root tagged with `marker`.
+pub fn spawn_overlay(
+    commands: &mut Commands,
+    marker: impl Component,
+    title: impl Into<String>,
+    hint: &str,
+    body: impl FnOnce(&mut ChildSpawnerCommands),
+) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(8.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.02, 0.02, 0.06, 0.95)),
+            GlobalZIndex(20),
+            marker,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(title),
+                TextFont { font_size: 36.0, ..default() },
+                TextColor(Color::WHITE),
+            ));
+            body(parent);
+            parent.spawn((
+                Text::new(hint),
+                TextFont { font_size: 16.0, ..default() },
+                TextColor(Color::srgb(0.5, 0.5, 0.5)),
+                TextLayout::new_with_justify(Justify::Center),
+            ));
+        });
+}
+
+pub fn back_pressed(keyboard: &ButtonInput<KeyCode>) -> bool {
+    keyboard.just_pressed(KeyCode::Escape) || keyboard.just_pressed(KeyCode::Backspace)
+}
+
+/// Input for overlays that only show information: back out to the default (closed) state.
+pub fn close_on_back<S: FreelyMutableState + Default>(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut menu_state: ResMut<NextState<S>>,
+) {
+    if back_pressed(&keyboard) {
+        menu_state.set(S::default());
+    }
+}
+
+pub fn despawn_overlay<T: Component>(mut commands: Commands, query: Query<Entity, With<T>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
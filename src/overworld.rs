@@ -1,5 +1,6 @@
 use bevy::prelude::*;
 use crate::components::*;
+use crate::events::{BattleStarted, CheckpointReached, GauntletCompleted};
 
 pub fn player_movement(
     keyboard: Res<ButtonInput<KeyCode>>,
@@ -32,50 +33,171 @@ pub fn player_movement(
     transform.translation.x = transform.translation.x.clamp(-110.0, 110.0);
 }
 
+pub fn cycle_equipment(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut equipment: ResMut<Equipment>,
+    mut text_query: Query<&mut Text, With<OverworldInstructions>>,
+) {
+    if keyboard.just_pressed(KeyCode::KeyQ) {
+        equipment.weapon = equipment.weapon.next();
+    } else if keyboard.just_pressed(KeyCode::KeyR) {
+        equipment.armor = equipment.armor.next();
+    } else {
+        return;
+    }
+
+    if let Ok(mut text) = text_query.single_mut() {
+        **text = format!(
+            "WASD: Move | Q: Weapon ({}) | R: Armor ({}) | Get close to enemies to battle!",
+            equipment.weapon.name(),
+            equipment.armor.name()
+        );
+    }
+}
+
+/// Walks each enemy along its room, chasing the player once they enter its view cone.
+pub fn patrol_enemies(
+    time: Res<Time>,
+    player_query: Query<&Transform, (With<Player>, Without<Enemy>)>,
+    mut enemy_query: Query<(&mut Transform, &mut Patrol, &Enemy)>,
+    rooms_query: Query<&Room>,
+) {
+    let Ok(player_transform) = player_query.single() else { return };
+    let dt = time.delta_secs();
+
+    for (mut transform, mut patrol, enemy) in enemy_query.iter_mut() {
+        let room_cleared = rooms_query.iter().any(|room| room.index == enemy.room_index && room.cleared);
+        if enemy.health <= 0 || room_cleared {
+            continue;
+        }
+
+        patrol.alert_cooldown = (patrol.alert_cooldown - dt).max(0.0);
+        let to_player = (player_transform.translation - transform.translation).truncate();
+        let sees_player = patrol.can_see(to_player);
+
+        match patrol.state {
+            PatrolState::Patrolling => {
+                if sees_player {
+                    patrol.state = PatrolState::Chasing;
+                    patrol.lost_for = 0.0;
+                } else {
+                    transform.translation.x += patrol.facing.x * PATROL_SPEED * dt;
+                    let offset = transform.translation.x - patrol.home.x;
+                    if offset.abs() > PATROL_HALF_WIDTH {
+                        transform.translation.x = patrol.home.x + offset.clamp(-PATROL_HALF_WIDTH, PATROL_HALF_WIDTH);
+                        patrol.facing = Vec2::new(-offset.signum(), 0.0);
+                    }
+                }
+            }
+            PatrolState::Chasing => {
+                patrol.facing = to_player.normalize_or(patrol.facing);
+                let step = patrol.facing * CHASE_SPEED * dt;
+                transform.translation += step.extend(0.0);
+
+                patrol.lost_for = if sees_player { 0.0 } else { patrol.lost_for + dt };
+                let strayed = transform.translation.distance(patrol.home) > CHASE_LEASH;
+                if patrol.lost_for > CHASE_GIVE_UP_SECS || strayed {
+                    patrol.state = PatrolState::Returning;
+                }
+            }
+            PatrolState::Returning => {
+                let to_home = (patrol.home - transform.translation).truncate();
+                if sees_player {
+                    patrol.state = PatrolState::Chasing;
+                    patrol.lost_for = 0.0;
+                } else if to_home.length() <= PATROL_SPEED * dt {
+                    transform.translation = patrol.home;
+                    patrol.facing = Vec2::X;
+                    patrol.state = PatrolState::Patrolling;
+                } else {
+                    patrol.facing = to_home.normalize();
+                    transform.translation += (patrol.facing * PATROL_SPEED * dt).extend(0.0);
+                }
+            }
+        }
+    }
+}
+
 pub fn check_room_transition(
-    player_query: Query<&Transform, With<Player>>,
-    enemy_query: Query<(Entity, &Transform, &Enemy), Without<Player>>,
+    player_query: Query<&Transform, (With<Player>, Without<Enemy>)>,
+    mut enemy_query: Query<(Entity, &Transform, &Enemy, &mut Patrol), Without<Player>>,
     rooms_query: Query<&Room>,
     mut battle_state: ResMut<CurrentBattle>,
     mut game_state: ResMut<NextState<GameState>>,
-    mut battle_ui: Query<&mut Visibility, With<BattleUI>>,
     mut game_progress: ResMut<GameProgress>,
+    mut battle_started: MessageWriter<BattleStarted>,
 ) {
     let Ok(player_transform) = player_query.single() else { return };
 
-    for (enemy_entity, enemy_transform, enemy) in enemy_query.iter() {
+    for (enemy_entity, enemy_transform, enemy, mut patrol) in enemy_query.iter_mut() {
         let distance = player_transform.translation.distance(enemy_transform.translation);
         let room_cleared = rooms_query.iter().any(|room| room.index == enemy.room_index && room.cleared);
 
-        if distance < 40.0 && enemy.health > 0 && !room_cleared {
+        if distance < ENCOUNTER_RADIUS && enemy.health > 0 && !room_cleared {
+            // A chasing enemy strikes first; walking into its back earns a free turn
+            let to_player = (player_transform.translation - enemy_transform.translation).truncate();
+            battle_state.initiative = if patrol.state == PatrolState::Chasing {
+                Initiative::Ambushed
+            } else if patrol.facing.dot(to_player) < 0.0 {
+                Initiative::Preemptive
+            } else {
+                Initiative::Player
+            };
+            patrol.state = PatrolState::Returning;
+            patrol.alert_cooldown = 2.0;
+
             battle_state.enemy_entity = enemy_entity;
             battle_state.phase = BattlePhase::Intro;
             battle_state.phase_timer = Timer::from_seconds(0.8, TimerMode::Once);
             battle_state.player_defended = false;
             battle_state.combo_count = 0;
+            battle_state.strikes_this_turn = 0;
+            battle_state.graze_meter = 0.0;
+            battle_state.graze_count = 0;
+            battle_state.rounds_survived = 0;
             game_progress.current_room = enemy.room_index;
 
-            for mut visibility in battle_ui.iter_mut() {
-                *visibility = Visibility::Visible;
-            }
-
+            battle_started.write(BattleStarted { enemy: enemy_entity, boss: enemy.boss });
             game_state.set(GameState::Battle);
             break;
         }
     }
 }
 
+pub fn check_checkpoints(
+    player_query: Query<&Transform, With<Player>>,
+    mut checkpoint_query: Query<(&Transform, &mut Checkpoint, &mut Sprite), Without<Player>>,
+    mut respawn: ResMut<RespawnPoint>,
+    mut reached: MessageWriter<CheckpointReached>,
+) {
+    let Ok(player_transform) = player_query.single() else { return };
+
+    for (transform, mut checkpoint, mut sprite) in checkpoint_query.iter_mut() {
+        // Spans the whole corridor so nobody can walk past without saving
+        let crossing = (player_transform.translation.y - transform.translation.y).abs() < 20.0;
+        if checkpoint.activated || !crossing {
+            continue;
+        }
+        checkpoint.activated = true;
+        sprite.color = Color::srgb(0.3, 1.0, 0.5);
+        respawn.position = transform.translation.with_z(PLAYER_START.z);
+        reached.write(CheckpointReached { index: checkpoint.index });
+    }
+}
+
 pub fn check_exit_door(
     player_query: Query<&Transform, With<Player>>,
     exit_query: Query<&Transform, (With<ExitDoor>, Without<Player>)>,
     game_progress: Res<GameProgress>,
     mut game_state: ResMut<NextState<GameState>>,
+    mut completed: MessageWriter<GauntletCompleted>,
 ) {
     let Ok(player_transform) = player_query.single() else { return };
     let Ok(exit_transform) = exit_query.single() else { return };
     let distance = player_transform.translation.distance(exit_transform.translation);
 
     if distance < 45.0 && game_progress.rooms_cleared >= game_progress.total_rooms {
+        completed.write(GauntletCompleted);
         game_state.set(GameState::Victory);
     }
 }
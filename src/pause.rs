@@ -0,0 +1,145 @@
+use bevy::prelude::*;
+use crate::components::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PauseAction {
+    Resume,
+    Settings,
+    QuestLog,
+    RestartRun,
+    QuitToTitle,
+}
+
+impl PauseAction {
+    fn label(&self) -> &'static str {
+        match self {
+            PauseAction::Resume => "Resume",
+            PauseAction::Settings => "Settings",
+            PauseAction::QuestLog => "Quest Log",
+            PauseAction::RestartRun => "Restart Run",
+            PauseAction::QuitToTitle => "Quit to Title",
+        }
+    }
+}
+
+const PAUSE_ITEMS: [PauseAction; 5] = [
+    PauseAction::Resume,
+    PauseAction::Settings,
+    PauseAction::QuestLog,
+    PauseAction::RestartRun,
+    PauseAction::QuitToTitle,
+];
+
+#[derive(Resource, Default)]
+pub struct PauseMenu {
+    pub selected: usize,
+}
+
+pub fn toggle_pause(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut pause_state: ResMut<NextState<PauseState>>,
+) {
+    if keyboard.just_pressed(KeyCode::Escape) {
+        pause_state.set(PauseState::Paused);
+    }
+}
+
+pub fn enter_pause(mut commands: Commands, mut time: ResMut<Time<Virtual>>, mut menu: ResMut<PauseMenu>) {
+    time.pause();
+    menu.selected = 0;
+
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(12.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.7)),
+            GlobalZIndex(10),
+            PauseMenuUI,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new("PAUSED"),
+                TextFont { font_size: 42.0, ..default() },
+                TextColor(Color::WHITE),
+            ));
+            for (i, action) in PAUSE_ITEMS.iter().enumerate() {
+                parent.spawn((
+                    Text::new(action.label()),
+                    TextFont { font_size: 26.0, ..default() },
+                    TextColor(Color::srgb(0.7, 0.7, 0.7)),
+                    PauseMenuItem(i),
+                ));
+            }
+        });
+}
+
+pub fn exit_pause(
+    mut commands: Commands,
+    mut time: ResMut<Time<Virtual>>,
+    query: Query<Entity, With<PauseMenuUI>>,
+) {
+    time.unpause();
+    for entity in query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+pub fn pause_menu_input(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut menu: ResMut<PauseMenu>,
+    mut commands: Commands,
+    mut pause_state: ResMut<NextState<PauseState>>,
+    mut game_state: ResMut<NextState<GameState>>,
+    mut settings_state: ResMut<NextState<SettingsMenuState>>,
+    mut quest_log_state: ResMut<NextState<QuestLogState>>,
+) {
+    if keyboard.just_pressed(KeyCode::Escape) {
+        pause_state.set(PauseState::Running);
+        return;
+    }
+
+    let count = PAUSE_ITEMS.len();
+    if keyboard.just_pressed(KeyCode::KeyW) || keyboard.just_pressed(KeyCode::ArrowUp) {
+        menu.selected = (menu.selected + count - 1) % count;
+    }
+    if keyboard.just_pressed(KeyCode::KeyS) || keyboard.just_pressed(KeyCode::ArrowDown) {
+        menu.selected = (menu.selected + 1) % count;
+    }
+
+    if !(keyboard.just_pressed(KeyCode::Space) || keyboard.just_pressed(KeyCode::Enter)) {
+        return;
+    }
+
+    match PAUSE_ITEMS[menu.selected] {
+        PauseAction::Resume => pause_state.set(PauseState::Running),
+        PauseAction::Settings => settings_state.set(SettingsMenuState::Open),
+        PauseAction::QuestLog => quest_log_state.set(QuestLogState::Open),
+        PauseAction::RestartRun => {
+            commands.run_system_cached(crate::reset_run);
+            pause_state.set(PauseState::Running);
+            game_state.set(GameState::Overworld);
+        }
+        PauseAction::QuitToTitle => {
+            commands.run_system_cached(crate::reset_run);
+            game_state.set(GameState::MainMenu);
+        }
+    }
+}
+
+pub fn update_pause_menu(menu: Res<PauseMenu>, mut items: Query<(&PauseMenuItem, &mut TextColor)>) {
+    for (item, mut color) in items.iter_mut() {
+        color.0 = if item.0 == menu.selected {
+            Color::srgb(1.0, 0.9, 0.3)
+        } else {
+            Color::srgb(0.7, 0.7, 0.7)
+        };
+    }
+}
@@ -0,0 +1,43 @@
+use bevy::asset::io::file::FileAssetReader;
+use bevy::prelude::*;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// Matches the default `AssetPlugin::file_path`.
+const ASSET_FOLDER: &str = "assets";
+
+/// Reads a file the game saved earlier, falling back to the default if it is missing or malformed.
+pub fn load_ron<T: DeserializeOwned + Default>(path: &str) -> T {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|contents| ron::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+/// Reads a data file that ships with the game; unlike saves, a missing or broken one is worth a warning.
+/// `path` is relative to the asset folder, resolved the same way the `AssetServer` resolves it, so
+/// launching from another working directory still finds the file.
+pub fn load_asset_ron<T: DeserializeOwned + Default>(path: &str) -> T {
+    let full_path = FileAssetReader::get_base_path().join(ASSET_FOLDER).join(path);
+    match std::fs::read_to_string(&full_path) {
+        Ok(contents) => ron::from_str(&contents).unwrap_or_else(|err| {
+            warn!("Failed to parse {}: {}", path, err);
+            T::default()
+        }),
+        Err(err) => {
+            warn!("Failed to read {}: {}", full_path.display(), err);
+            T::default()
+        }
+    }
+}
+
+pub fn save_ron<T: Serialize>(path: &str, value: &T) {
+    match ron::ser::to_string_pretty(value, ron::ser::PrettyConfig::default()) {
+        Ok(contents) => {
+            if let Err(err) = std::fs::write(path, contents) {
+                warn!("Failed to write {}: {}", path, err);
+            }
+        }
+        Err(err) => warn!("Failed to serialize {}: {}", path, err),
+    }
+}
@@ -0,0 +1,210 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+use crate::achievements::{spawn_toast, Toast};
+use crate::components::*;
+use crate::dialogue::{apply_effects, conditions_met, Condition, Effect};
+use crate::events::{BattleEnded, BattleOutcome, BattleStarted, DamageSource, EnemyDamaged};
+use crate::flags::GameFlags;
+
+const QUESTS_PATH: &str = "quests.ron";
+
+#[derive(Debug, Clone, Deserialize)]
+pub enum Objective {
+    Defeat(String),
+    /// Win the fight without a single strike landing; parried bullets and status damage are fine.
+    DefeatWithoutAttacking(String),
+    Flag(String),
+    CounterAtLeast(String, i32),
+    RoomsCleared(usize),
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ObjectiveDef {
+    pub description: String,
+    pub goal: Objective,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct QuestDef {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    #[serde(default)]
+    pub starts_when: Vec<Condition>,
+    pub objectives: Vec<ObjectiveDef>,
+    #[serde(default)]
+    pub rewards: Vec<Effect>,
+}
+
+impl QuestDef {
+    fn status_flag(&self) -> String {
+        format!("run:quest:{}", self.id)
+    }
+
+    fn objective_flag(&self, index: usize) -> String {
+        format!("run:quest:{}:{}", self.id, index)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuestStatus {
+    Hidden,
+    Active,
+    Complete,
+}
+
+#[derive(Resource, Default)]
+pub struct Quests {
+    pub defs: Vec<QuestDef>,
+}
+
+impl Quests {
+    pub fn load() -> Self {
+        let defs = crate::persist::load_asset_ron(QUESTS_PATH);
+        Self { defs }
+    }
+
+    pub fn status(quest: &QuestDef, flags: &GameFlags) -> QuestStatus {
+        match flags.text(&quest.status_flag()) {
+            Some("complete") => QuestStatus::Complete,
+            Some("active") => QuestStatus::Active,
+            _ => QuestStatus::Hidden,
+        }
+    }
+}
+
+/// Whether the player has struck the enemy in the current fight.
+#[derive(Resource, Default)]
+pub struct QuestTracker {
+    pub attacked_this_battle: bool,
+}
+
+#[derive(Component)]
+pub struct QuestLogUI;
+
+/// Marks pacifist wins the moment a fight ends, since that can't be read back from flags afterwards.
+pub fn track_quest_battles(
+    mut started: MessageReader<BattleStarted>,
+    mut damaged: MessageReader<EnemyDamaged>,
+    mut ended: MessageReader<BattleEnded>,
+    enemy_query: Query<&Enemy>,
+    quests: Res<Quests>,
+    mut tracker: ResMut<QuestTracker>,
+    mut flags: ResMut<GameFlags>,
+) {
+    if started.read().count() > 0 {
+        tracker.attacked_this_battle = false;
+    }
+    if damaged.read().any(|event| event.source == DamageSource::Strike) {
+        tracker.attacked_this_battle = true;
+    }
+
+    for event in ended.read() {
+        if event.outcome != BattleOutcome::Won || tracker.attacked_this_battle {
+            continue;
+        }
+        let Ok(enemy) = enemy_query.get(event.enemy) else { continue };
+        for quest in quests.defs.iter() {
+            if Quests::status(quest, &flags) != QuestStatus::Active {
+                continue;
+            }
+            for (index, objective) in quest.objectives.iter().enumerate() {
+                if matches!(&objective.goal, Objective::DefeatWithoutAttacking(name) if name == enemy.name) {
+                    flags.set_bool(&quest.objective_flag(index), true);
+                }
+            }
+        }
+    }
+}
+
+/// Starts quests whose conditions now hold, ticks off objectives and pays out finished quests.
+pub fn update_quests(
+    mut commands: Commands,
+    quests: Res<Quests>,
+    game_progress: Res<GameProgress>,
+    mut inventory: ResMut<Inventory>,
+    mut flags: ResMut<GameFlags>,
+    toasts: Query<(), With<Toast>>,
+) {
+    let mut slot = toasts.iter().count();
+
+    for quest in quests.defs.iter() {
+        match Quests::status(quest, &flags) {
+            QuestStatus::Complete => continue,
+            QuestStatus::Hidden => {
+                if !conditions_met(&quest.starts_when, &game_progress, &inventory, &flags) {
+                    continue;
+                }
+                flags.set_text(&quest.status_flag(), "active");
+                spawn_toast(&mut commands, slot, "NEW QUEST", quest.name.clone());
+                slot += 1;
+            }
+            QuestStatus::Active => {}
+        }
+
+        for (index, objective) in quest.objectives.iter().enumerate() {
+            let flag = quest.objective_flag(index);
+            if flags.is_set(&flag) {
+                continue;
+            }
+            let done = match &objective.goal {
+                Objective::Defeat(enemy) => flags.is_set(&format!("run:defeated:{}", enemy)),
+                Objective::DefeatWithoutAttacking(_) => false,
+                Objective::Flag(name) => flags.is_set(name),
+                Objective::CounterAtLeast(name, value) => flags.counter(name) >= *value,
+                Objective::RoomsCleared(rooms) => game_progress.rooms_cleared >= *rooms,
+            };
+            if done {
+                flags.set_bool(&flag, true);
+            }
+        }
+
+        let finished = (0..quest.objectives.len()).all(|index| flags.is_set(&quest.objective_flag(index)));
+        if finished {
+            flags.set_text(&quest.status_flag(), "complete");
+            apply_effects(&quest.rewards, &mut inventory, &mut flags);
+            spawn_toast(&mut commands, slot, "QUEST COMPLETE", quest.name.clone());
+            slot += 1;
+        }
+    }
+}
+
+fn quest_entry(quest: &QuestDef, flags: &GameFlags) -> String {
+    let mut entry = format!("{}\n  {}", quest.name, quest.description);
+    for (index, objective) in quest.objectives.iter().enumerate() {
+        let mark = if flags.is_set(&quest.objective_flag(index)) { "[x]" } else { "[ ]" };
+        entry.push_str(&format!("\n  {} {}", mark, objective.description));
+    }
+    entry
+}
+
+pub fn open_quest_log(mut commands: Commands, quests: Res<Quests>, flags: Res<GameFlags>) {
+    let section = |status: QuestStatus| {
+        let entries: Vec<String> = quests
+            .defs
+            .iter()
+            .filter(|quest| Quests::status(quest, &flags) == status)
+            .map(|quest| quest_entry(quest, &flags))
+            .collect();
+        if entries.is_empty() { "  None".to_string() } else { entries.join("\n\n") }
+    };
+
+    crate::overlay::spawn_overlay(
+        &mut commands,
+        QuestLogUI,
+        "QUEST LOG",
+        "[ESC] Back",
+        |parent| {
+            parent.spawn((
+                Text::new(format!("ACTIVE\n{}", section(QuestStatus::Active))),
+                TextFont { font_size: 18.0, ..default() },
+                TextColor(Color::srgb(1.0, 0.9, 0.5)),
+            ));
+            parent.spawn((
+                Text::new(format!("COMPLETED\n{}", section(QuestStatus::Complete))),
+                TextFont { font_size: 18.0, ..default() },
+                TextColor(Color::srgb(0.6, 0.8, 0.6)),
+            ));
+        },
+    );
+}
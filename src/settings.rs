@@ -0,0 +1,243 @@
+use bevy::prelude::*;
+use bevy::window::{MonitorSelection, PresentMode, PrimaryWindow, VideoModeSelection, WindowMode};
+use serde::{Deserialize, Serialize};
+use crate::accessibility::{AssistMode, Palette};
+use crate::components::*;
+
+const SETTINGS_PATH: &str = "settings.ron";
+const RESOLUTIONS: [(u32, u32); 3] = [(1280, 720), (1600, 900), (1920, 1080)];
+const SETTINGS_ROWS: usize = 14;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DisplayMode {
+    Windowed,
+    Borderless,
+    Fullscreen,
+}
+
+impl DisplayMode {
+    fn window_mode(&self) -> WindowMode {
+        match self {
+            DisplayMode::Windowed => WindowMode::Windowed,
+            DisplayMode::Borderless => WindowMode::BorderlessFullscreen(MonitorSelection::Current),
+            DisplayMode::Fullscreen => WindowMode::Fullscreen(MonitorSelection::Current, VideoModeSelection::Current),
+        }
+    }
+
+    fn next(&self) -> DisplayMode {
+        match self {
+            DisplayMode::Windowed => DisplayMode::Borderless,
+            DisplayMode::Borderless => DisplayMode::Fullscreen,
+            DisplayMode::Fullscreen => DisplayMode::Windowed,
+        }
+    }
+}
+
+#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub display_mode: DisplayMode,
+    pub resolution: (u32, u32),
+    pub vsync: bool,
+    pub master_volume: f32,
+    pub music_volume: f32,
+    pub sfx_volume: f32,
+    pub screen_shake: f32,
+    pub bullet_speed: f32,
+    pub text_scale: f32,
+    pub reduce_motion: bool,
+    pub reduce_flashing: bool,
+    pub high_contrast_bullets: bool,
+    pub palette: Palette,
+    pub assist: AssistMode,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            display_mode: DisplayMode::Windowed,
+            resolution: (1280, 720),
+            vsync: true,
+            master_volume: 0.8,
+            music_volume: 0.7,
+            sfx_volume: 0.8,
+            screen_shake: 1.0,
+            bullet_speed: 1.0,
+            text_scale: 1.0,
+            reduce_motion: false,
+            reduce_flashing: false,
+            high_contrast_bullets: false,
+            palette: Palette::Default,
+            assist: AssistMode::Off,
+        }
+    }
+}
+
+impl Settings {
+    pub fn load() -> Self {
+        crate::persist::load_ron(SETTINGS_PATH)
+    }
+
+    pub fn save(&self) {
+        crate::persist::save_ron(SETTINGS_PATH, self);
+    }
+
+    pub fn window(&self) -> Window {
+        Window {
+            title: "Dungeon Gauntlet".to_string(),
+            resolution: self.resolution.into(),
+            mode: self.display_mode.window_mode(),
+            present_mode: self.present_mode(),
+            ..default()
+        }
+    }
+
+    fn present_mode(&self) -> PresentMode {
+        if self.vsync { PresentMode::AutoVsync } else { PresentMode::AutoNoVsync }
+    }
+
+    fn row_label(&self, row: usize) -> String {
+        match row {
+            0 => format!("Window Mode: {:?}", self.display_mode),
+            1 => format!("Resolution: {}x{}", self.resolution.0, self.resolution.1),
+            2 => format!("VSync: {}", if self.vsync { "On" } else { "Off" }),
+            3 => format!("Master Volume: {:.0}%", self.master_volume * 100.0),
+            4 => format!("Music Volume: {:.0}%", self.music_volume * 100.0),
+            5 => format!("SFX Volume: {:.0}%", self.sfx_volume * 100.0),
+            6 => format!("Screen Shake: {:.0}%", self.screen_shake * 100.0),
+            7 => format!("Bullet Speed: {:.0}%", self.bullet_speed * 100.0),
+            8 => format!("Text Size: {:.0}%", self.text_scale * 100.0),
+            9 => format!("Reduce Motion: {}", if self.reduce_motion { "On" } else { "Off" }),
+            10 => format!("Reduce Flashing: {}", if self.reduce_flashing { "On" } else { "Off" }),
+            11 => format!("High-Contrast Bullets: {}", if self.high_contrast_bullets { "On" } else { "Off" }),
+            12 => format!("Color Palette: {:?}", self.palette),
+            _ => format!("Assist Mode: {:?}", self.assist),
+        }
+    }
+
+    fn adjust(&mut self, row: usize, step: f32) {
+        match row {
+            0 => self.display_mode = self.display_mode.next(),
+            1 => {
+                let current = RESOLUTIONS.iter().position(|r| *r == self.resolution).unwrap_or(0);
+                let count = RESOLUTIONS.len() as i32;
+                let next = (current as i32 + step.signum() as i32).rem_euclid(count);
+                self.resolution = RESOLUTIONS[next as usize];
+            }
+            2 => self.vsync = !self.vsync,
+            3 => self.master_volume = (self.master_volume + step * 0.1).clamp(0.0, 1.0),
+            4 => self.music_volume = (self.music_volume + step * 0.1).clamp(0.0, 1.0),
+            5 => self.sfx_volume = (self.sfx_volume + step * 0.1).clamp(0.0, 1.0),
+            6 => self.screen_shake = (self.screen_shake + step * 0.25).clamp(0.0, 1.0),
+            7 => self.bullet_speed = (self.bullet_speed + step * 0.1).clamp(0.5, 1.5),
+            8 => self.text_scale = (self.text_scale + step * 0.1).clamp(0.8, 1.5),
+            9 => self.reduce_motion = !self.reduce_motion,
+            10 => self.reduce_flashing = !self.reduce_flashing,
+            11 => self.high_contrast_bullets = !self.high_contrast_bullets,
+            12 => self.palette = self.palette.next(),
+            _ => self.assist = self.assist.next(),
+        }
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct SettingsMenu {
+    pub selected: usize,
+}
+
+#[derive(Component)]
+pub struct SettingsMenuUI;
+
+#[derive(Component)]
+pub struct SettingsRow(pub usize);
+
+pub fn apply_settings(
+    settings: Res<Settings>,
+    mut window_query: Query<&mut Window, With<PrimaryWindow>>,
+    mut ui_scale: ResMut<UiScale>,
+) {
+    ui_scale.0 = settings.text_scale;
+
+    let Ok(mut window) = window_query.single_mut() else { return };
+    let mode = settings.display_mode.window_mode();
+    if window.mode != mode {
+        window.mode = mode;
+    }
+    let (width, height) = settings.resolution;
+    if window.resolution.width() != width as f32 || window.resolution.height() != height as f32 {
+        window.resolution.set(width as f32, height as f32);
+    }
+    window.present_mode = settings.present_mode();
+}
+
+pub fn open_settings(mut commands: Commands, mut menu: ResMut<SettingsMenu>, settings: Res<Settings>) {
+    menu.selected = 0;
+
+    crate::overlay::spawn_overlay(
+        &mut commands,
+        SettingsMenuUI,
+        "SETTINGS",
+        "[W/S] Select | [A/D] Change | [ESC] Back",
+        |parent| {
+            for row in 0..SETTINGS_ROWS {
+                parent.spawn((
+                    Text::new(settings.row_label(row)),
+                    TextFont { font_size: 20.0, ..default() },
+                    TextColor(Color::srgb(0.7, 0.7, 0.7)),
+                    SettingsRow(row),
+                ));
+            }
+        },
+    );
+}
+
+pub fn close_settings(
+    mut commands: Commands,
+    settings: Res<Settings>,
+    query: Query<Entity, With<SettingsMenuUI>>,
+) {
+    settings.save();
+    for entity in query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+pub fn settings_menu_input(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut menu: ResMut<SettingsMenu>,
+    mut settings: ResMut<Settings>,
+    mut menu_state: ResMut<NextState<SettingsMenuState>>,
+) {
+    if crate::overlay::back_pressed(&keyboard) {
+        menu_state.set(SettingsMenuState::Closed);
+        return;
+    }
+
+    if keyboard.just_pressed(KeyCode::KeyW) || keyboard.just_pressed(KeyCode::ArrowUp) {
+        menu.selected = (menu.selected + SETTINGS_ROWS - 1) % SETTINGS_ROWS;
+    }
+    if keyboard.just_pressed(KeyCode::KeyS) || keyboard.just_pressed(KeyCode::ArrowDown) {
+        menu.selected = (menu.selected + 1) % SETTINGS_ROWS;
+    }
+    if keyboard.just_pressed(KeyCode::KeyA) || keyboard.just_pressed(KeyCode::ArrowLeft) {
+        settings.adjust(menu.selected, -1.0);
+    }
+    if keyboard.just_pressed(KeyCode::KeyD) || keyboard.just_pressed(KeyCode::ArrowRight) {
+        settings.adjust(menu.selected, 1.0);
+    }
+}
+
+pub fn update_settings_menu(
+    menu: Res<SettingsMenu>,
+    settings: Res<Settings>,
+    mut rows: Query<(&SettingsRow, &mut Text, &mut TextColor)>,
+) {
+    for (row, mut text, mut color) in rows.iter_mut() {
+        **text = settings.row_label(row.0);
+        color.0 = if row.0 == menu.selected {
+            Color::srgb(1.0, 0.9, 0.3)
+        } else {
+            Color::srgb(0.7, 0.7, 0.7)
+        };
+    }
+}
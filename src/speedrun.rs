@@ -0,0 +1,279 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::components::*;
+use crate::difficulty::{Difficulty, DifficultyPreset};
+use crate::enemies::ENEMY_ROSTER;
+use crate::events::{GauntletCompleted, RoomCleared};
+use crate::transitions::BattleTransitionFx;
+
+const PERSONAL_BEST_PATH: &str = "personal_best.ron";
+const SPLITS_EXPORT_PATH: &str = "splits.lss";
+/// One segment per room plus the walk to the exit door.
+const SEGMENTS: usize = TOTAL_ROOMS + 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TimerState {
+    #[default]
+    Idle,
+    Running,
+    Finished,
+}
+
+/// Game time for the current attempt, and the run's only clock: the end screens,
+/// score and leaderboard all read `elapsed`. Only ticks in the overworld and in
+/// battle, and reads virtual time so pausing stops it along with everything else.
+#[derive(Resource, Default)]
+pub struct SpeedrunTimer {
+    pub state: TimerState,
+    pub elapsed: f32,
+    /// Indexed by segment: rooms by their index, then the exit door.
+    pub splits: [Option<f32>; SEGMENTS],
+    pub last_split: Option<usize>,
+}
+
+impl SpeedrunTimer {
+    fn split(&mut self, segment: usize) {
+        if self.state == TimerState::Running && self.splits[segment].is_none() {
+            self.splits[segment] = Some(self.elapsed);
+            self.last_split = Some(segment);
+        }
+    }
+}
+
+/// Bests for one category. Runs only race others on the same difficulty and mode.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PersonalBest {
+    pub difficulty: DifficultyPreset,
+    pub mode: RunMode,
+    pub attempts: u32,
+    /// Cumulative split times of the fastest finished run; empty until one exists.
+    pub splits: Vec<f32>,
+    /// Fastest time seen for each segment on its own, across all runs.
+    pub best_segments: Vec<Option<f32>>,
+}
+
+#[derive(Resource, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PersonalBests {
+    pub categories: Vec<PersonalBest>,
+}
+
+impl PersonalBests {
+    pub fn load() -> Self {
+        crate::persist::load_ron(PERSONAL_BEST_PATH)
+    }
+
+    pub fn save(&self) {
+        crate::persist::save_ron(PERSONAL_BEST_PATH, self);
+    }
+
+    fn get(&self, difficulty: DifficultyPreset, mode: RunMode) -> Option<&PersonalBest> {
+        self.categories
+            .iter()
+            .find(|best| best.difficulty == difficulty && best.mode == mode)
+    }
+
+    fn entry(&mut self, difficulty: DifficultyPreset, mode: RunMode) -> &mut PersonalBest {
+        let index = self
+            .categories
+            .iter()
+            .position(|best| best.difficulty == difficulty && best.mode == mode)
+            .unwrap_or_else(|| {
+                self.categories.push(PersonalBest { difficulty, mode, ..default() });
+                self.categories.len() - 1
+            });
+        &mut self.categories[index]
+    }
+}
+
+impl PersonalBest {
+    fn category(&self) -> String {
+        format!("{:?} {}", self.difficulty, self.mode.label())
+    }
+
+    fn delta(&self, segment: usize, time: f32) -> Option<f32> {
+        self.splits.get(segment).map(|best| time - best)
+    }
+
+    /// Folds a finished run in: keeps any gold segments and replaces the PB if it was faster.
+    fn record(&mut self, splits: &[Option<f32>]) {
+        self.best_segments.resize(SEGMENTS, None);
+        let mut previous = Some(0.0);
+        for (segment, split) in splits.iter().enumerate() {
+            if let (Some(start), Some(end)) = (previous, split) {
+                let duration = end - start;
+                let best = &mut self.best_segments[segment];
+                if best.is_none_or(|best| duration < best) {
+                    *best = Some(duration);
+                }
+            }
+            previous = *split;
+        }
+
+        let Some(times) = splits.iter().copied().collect::<Option<Vec<f32>>>() else { return };
+        let faster = match (self.splits.last(), times.last()) {
+            (Some(best), Some(time)) => time < best,
+            _ => true,
+        };
+        if faster {
+            self.splits = times;
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct SpeedrunText;
+
+fn segment_name(segment: usize) -> String {
+    match ENEMY_ROSTER.get(segment) {
+        Some(def) if segment < TOTAL_ROOMS => format!("Room {} - {}", segment + 1, def.name),
+        _ => "Exit Door".to_string(),
+    }
+}
+
+pub fn format_split(secs: f32) -> String {
+    let centis = (secs.max(0.0) * 100.0).round() as u32;
+    format!("{}:{:02}.{:02}", centis / 6000, centis / 100 % 60, centis % 100)
+}
+
+fn format_delta(delta: f32) -> String {
+    let sign = if delta < 0.0 { '-' } else { '+' };
+    format!("{}{:.2}", sign, delta.abs())
+}
+
+/// LiveSplit time strings are `hh:mm:ss.fffffff`.
+fn lss_time(secs: f32) -> String {
+    let ticks = (secs.max(0.0) as f64 * 10_000_000.0).round() as u64;
+    let total_secs = ticks / 10_000_000;
+    format!(
+        "{:02}:{:02}:{:02}.{:07}",
+        total_secs / 3600,
+        total_secs / 60 % 60,
+        total_secs % 60,
+        ticks % 10_000_000
+    )
+}
+
+fn lss_times(secs: f32) -> String {
+    let time = lss_time(secs);
+    format!("<RealTime>{}</RealTime><GameTime>{}</GameTime>", time, time)
+}
+
+/// Writes the personal best as a LiveSplit splits file.
+fn export_lss(best: &PersonalBest) {
+    let mut segments = String::new();
+    for segment in 0..SEGMENTS {
+        let split = best
+            .splits
+            .get(segment)
+            .map(|time| format!("<SplitTime name=\"Personal Best\">{}</SplitTime>", lss_times(*time)))
+            .unwrap_or_else(|| "<SplitTime name=\"Personal Best\" />".to_string());
+        let gold = best
+            .best_segments
+            .get(segment)
+            .copied()
+            .flatten()
+            .map(|time| format!("<BestSegmentTime>{}</BestSegmentTime>", lss_times(time)))
+            .unwrap_or_else(|| "<BestSegmentTime />".to_string());
+        segments.push_str(&format!(
+            "    <Segment>\n      <Name>{}</Name>\n      <Icon />\n      <SplitTimes>{}</SplitTimes>\n      {}\n      <SegmentHistory />\n    </Segment>\n",
+            segment_name(segment),
+            split,
+            gold
+        ));
+    }
+
+    let contents = format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<Run version=\"1.7.0\">\n  <GameIcon />\n  <GameName>Dungeon Gauntlet</GameName>\n  <CategoryName>{}</CategoryName>\n  <Offset>00:00:00</Offset>\n  <AttemptCount>{}</AttemptCount>\n  <AttemptHistory />\n  <Segments>\n{}  </Segments>\n  <AutoSplitterSettings />\n</Run>\n",
+        best.category(),
+        best.attempts,
+        segments
+    );
+    if let Err(err) = std::fs::write(SPLITS_EXPORT_PATH, contents) {
+        warn!("Failed to write {}: {}", SPLITS_EXPORT_PATH, err);
+    }
+}
+
+/// Starts a fresh attempt on the first gameplay frame after a reset, and ticks it after that.
+/// A results card left waiting for confirmation counts as menu time.
+pub fn tick_speedrun(
+    time: Res<Time>,
+    transition: Option<Res<State<BattleTransition>>>,
+    fx: Res<BattleTransitionFx>,
+    difficulty: Res<Difficulty>,
+    mode: Res<RunMode>,
+    mut timer: ResMut<SpeedrunTimer>,
+    mut bests: ResMut<PersonalBests>,
+) {
+    let on_results_card = transition.is_some_and(|state| *state.get() == BattleTransition::Exiting)
+        && fx.await_confirm
+        && fx.timer.is_finished();
+    if on_results_card {
+        return;
+    }
+
+    match timer.state {
+        TimerState::Idle => {
+            timer.state = TimerState::Running;
+            bests.entry(difficulty.preset, *mode).attempts += 1;
+            bests.save();
+        }
+        TimerState::Running => timer.elapsed += time.delta_secs(),
+        TimerState::Finished => {}
+    }
+}
+
+pub fn record_splits(
+    mut cleared: MessageReader<RoomCleared>,
+    mut completed: MessageReader<GauntletCompleted>,
+    difficulty: Res<Difficulty>,
+    mode: Res<RunMode>,
+    mut timer: ResMut<SpeedrunTimer>,
+    mut bests: ResMut<PersonalBests>,
+) {
+    for event in cleared.read() {
+        timer.split(event.room);
+    }
+
+    if completed.read().count() == 0 || timer.state != TimerState::Running {
+        return;
+    }
+    timer.split(SEGMENTS - 1);
+    timer.state = TimerState::Finished;
+    let best = bests.entry(difficulty.preset, *mode);
+    best.record(&timer.splits);
+    export_lss(best);
+    bests.save();
+}
+
+pub fn update_speedrun_text(
+    state: Res<State<GameState>>,
+    difficulty: Res<Difficulty>,
+    mode: Res<RunMode>,
+    timer: Res<SpeedrunTimer>,
+    bests: Res<PersonalBests>,
+    mut text_query: Query<(&mut Text, &mut TextColor, &mut Visibility), With<SpeedrunText>>,
+) {
+    let Ok((mut text, mut color, mut visibility)) = text_query.single_mut() else { return };
+    if *state.get() == GameState::MainMenu || timer.state == TimerState::Idle {
+        *visibility = Visibility::Hidden;
+        return;
+    }
+    *visibility = Visibility::Visible;
+
+    let mut label = format_split(timer.elapsed);
+    color.0 = Color::WHITE;
+    if let Some(segment) = timer.last_split {
+        let split = timer.splits[segment].unwrap_or_default();
+        label = format!("{}\n{}  {}", label, segment_name(segment), format_split(split));
+        if let Some(delta) = bests.get(difficulty.preset, *mode).and_then(|best| best.delta(segment, split)) {
+            label = format!("{}  {}", label, format_delta(delta));
+            color.0 = if delta < 0.0 { Color::srgb(0.4, 1.0, 0.5) } else { Color::srgb(1.0, 0.45, 0.4) };
+        }
+    }
+    if timer.state == TimerState::Finished {
+        label = format!("{}\nSplits saved to {}", label, SPLITS_EXPORT_PATH);
+    }
+    **text = label;
+}
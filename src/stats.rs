@@ -0,0 +1,206 @@
+use bevy::prelude::*;
+use crate::ai::EnemyAi;
+use crate::components::*;
+use crate::difficulty::Difficulty;
+use crate::enemies::ENEMY_ROSTER;
+use crate::events::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Grade {
+    S,
+    A,
+    B,
+    C,
+    D,
+}
+
+impl Grade {
+    pub fn letter(&self) -> &'static str {
+        match self {
+            Grade::S => "S",
+            Grade::A => "A",
+            Grade::B => "B",
+            Grade::C => "C",
+            Grade::D => "D",
+        }
+    }
+
+    pub fn xp_multiplier(&self) -> f32 {
+        match self {
+            Grade::S => 2.0,
+            Grade::A => 1.5,
+            Grade::B => 1.2,
+            Grade::C => 1.0,
+            Grade::D => 0.8,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BattleResult {
+    pub grade: Grade,
+    pub xp: u32,
+    pub loot: Vec<&'static str>,
+}
+
+/// Everything that happened in the current fight, rebuilt from gameplay messages.
+#[derive(Resource, Default)]
+pub struct BattleStats {
+    pub turns: u32,
+    pub damage_dealt: i32,
+    pub damage_taken: i32,
+    pub strikes: u32,
+    pub perfects: u32,
+    pub grazes: u32,
+    pub parries: u32,
+    pub best_combo: usize,
+    pub elapsed: f32,
+    pub last_phase: Option<BattlePhase>,
+    pub result: Option<BattleResult>,
+}
+
+impl BattleStats {
+    /// Scores the fight: perfect hits and grazes raise it, damage taken and long fights lower it.
+    pub fn grade(&self, max_health: i32) -> Grade {
+        let perfect_ratio = if self.strikes > 0 { self.perfects as f32 / self.strikes as f32 } else { 0.0 };
+        let score = 100.0
+            - self.damage_taken as f32 / max_health.max(1) as f32 * 60.0
+            + perfect_ratio * 30.0
+            + self.grazes.min(10) as f32
+            - self.turns.saturating_sub(3) as f32 * 5.0;
+
+        if score >= 110.0 {
+            Grade::S
+        } else if score >= 90.0 {
+            Grade::A
+        } else if score >= 70.0 {
+            Grade::B
+        } else if score >= 50.0 {
+            Grade::C
+        } else {
+            Grade::D
+        }
+    }
+}
+
+/// Totals across the whole run, shown on the game over and victory screens.
+#[derive(Resource, Default)]
+pub struct RunStats {
+    pub deaths: u32,
+    pub battles_won: u32,
+    pub turns: u32,
+    pub damage_dealt: i32,
+    pub damage_taken: i32,
+    pub perfects: u32,
+    pub grazes: u32,
+    pub bullets_dodged: u32,
+    pub best_combo: usize,
+    pub battle_time: f32,
+    pub xp: u32,
+    pub loot: Vec<&'static str>,
+}
+
+impl RunStats {
+    /// Damage, perfect hits, grazes and dodged bullets earn points, plus a bonus for
+    /// finishing inside ten minutes; the total is scaled by how hard the run was.
+    /// `run_time` comes from the speedrun timer, the run's one clock.
+    pub fn score(&self, difficulty: &Difficulty, run_time: f32) -> u32 {
+        let time_bonus = (600.0 - run_time).max(0.0) * 10.0;
+        let base = self.damage_dealt.max(0) as f32 * 10.0
+            + self.perfects as f32 * 100.0
+            + self.grazes as f32 * 25.0
+            + self.bullets_dodged as f32 * 10.0
+            + time_bonus;
+        (base * difficulty.score_multiplier()).round() as u32
+    }
+}
+
+pub fn format_time(secs: f32) -> String {
+    let total = secs.max(0.0) as u32;
+    format!("{}:{:02}", total / 60, total % 60)
+}
+
+pub fn reset_battle_stats(mut stats: ResMut<BattleStats>) {
+    *stats = BattleStats::default();
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn track_battle_stats(
+    time: Res<Time>,
+    battle_state: Res<CurrentBattle>,
+    transition: Res<State<BattleTransition>>,
+    mut stats: ResMut<BattleStats>,
+    mut rated: MessageReader<AttackRated>,
+    mut enemy_damaged: MessageReader<EnemyDamaged>,
+    mut player_damaged: MessageReader<PlayerDamaged>,
+    mut grazed: MessageReader<BulletGrazed>,
+    mut deflected: MessageReader<BulletDeflected>,
+) {
+    if *transition.get() == BattleTransition::Active {
+        stats.elapsed += time.delta_secs();
+        if battle_state.phase == BattlePhase::PlayerTurn && stats.last_phase != Some(BattlePhase::PlayerTurn) {
+            stats.turns += 1;
+        }
+        stats.last_phase = Some(battle_state.phase);
+    }
+
+    for rated in rated.read() {
+        stats.strikes += 1;
+        if rated.rating == Rating::Perfect {
+            stats.perfects += 1;
+        }
+        stats.best_combo = stats.best_combo.max(rated.combo);
+    }
+    for hit in enemy_damaged.read() {
+        stats.damage_dealt += hit.amount;
+    }
+    for hit in player_damaged.read() {
+        stats.damage_taken += hit.amount;
+    }
+    stats.grazes += grazed.read().count() as u32;
+    stats.parries += deflected.read().filter(|d| d.parried).count() as u32;
+}
+
+/// Grades a won fight, hands out XP and loot, and folds the fight into the run totals.
+pub fn finish_battle(
+    mut ended: MessageReader<BattleEnded>,
+    mut stats: ResMut<BattleStats>,
+    mut run: ResMut<RunStats>,
+    mut inventory: ResMut<Inventory>,
+    enemy_query: Query<&Enemy>,
+    ai: Res<EnemyAi>,
+) {
+    for event in ended.read() {
+        run.turns += stats.turns;
+        run.damage_dealt += stats.damage_dealt;
+        run.damage_taken += stats.damage_taken;
+        run.perfects += stats.perfects;
+        run.grazes += stats.grazes;
+        run.bullets_dodged += ai.bullets_dodged();
+        run.best_combo = run.best_combo.max(stats.best_combo);
+        run.battle_time += stats.elapsed;
+
+        if event.outcome == BattleOutcome::Lost {
+            run.deaths += 1;
+        }
+        if event.outcome != BattleOutcome::Won {
+            continue;
+        }
+        let Ok(enemy) = enemy_query.get(event.enemy) else { continue };
+
+        let grade = stats.grade(PLAYER_MAX_HEALTH);
+        let xp = (enemy.max_health as f32 * 2.0 * grade.xp_multiplier()).round() as u32;
+        let mut loot = vec![ENEMY_ROSTER[enemy.room_index].loot];
+        if matches!(grade, Grade::S | Grade::A) {
+            loot.push("Potion");
+        }
+
+        for item in &loot {
+            inventory.add(item, 1);
+        }
+        run.battles_won += 1;
+        run.xp += xp;
+        run.loot.extend(loot.iter().copied());
+        stats.result = Some(BattleResult { grade, xp, loot });
+    }
+}
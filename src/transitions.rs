@@ -0,0 +1,236 @@
+use bevy::prelude::*;
+use crate::components::*;
+use crate::events::{BattleEnded, BattleOutcome};
+use crate::settings::Settings;
+use crate::stats::{format_time, BattleStats};
+
+const ENTER_SECS: f32 = 0.9;
+const EXIT_SECS: f32 = 1.6;
+const ENTER_ZOOM: f32 = 0.5;
+
+#[derive(Resource)]
+pub struct BattleTransitionFx {
+    pub timer: Timer,
+    /// Set once the wipe has covered the screen and the arena has been built.
+    pub revealed: bool,
+    pub exit_to: GameState,
+    pub outcome: BattleOutcome,
+    /// Won fights hold the results card until the player confirms.
+    pub await_confirm: bool,
+}
+
+impl Default for BattleTransitionFx {
+    fn default() -> Self {
+        Self {
+            timer: Timer::from_seconds(ENTER_SECS, TimerMode::Once),
+            revealed: false,
+            exit_to: GameState::Overworld,
+            outcome: BattleOutcome::Fled,
+            await_confirm: false,
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct TransitionOverlay;
+
+#[derive(Component)]
+pub struct ResultsCard;
+
+pub fn start_enter(mut commands: Commands, mut fx: ResMut<BattleTransitionFx>) {
+    fx.timer = Timer::from_seconds(ENTER_SECS, TimerMode::Once);
+    fx.revealed = false;
+
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Percent(0.0),
+            width: Val::Percent(0.0),
+            height: Val::Percent(100.0),
+            ..default()
+        },
+        BackgroundColor(Color::BLACK),
+        GlobalZIndex(5),
+        TransitionOverlay,
+        BattleSprite,
+    ));
+}
+
+/// Zooms in on the engaged enemy while a wipe covers the screen, builds the
+/// arena behind it, then wipes it back open.
+#[allow(clippy::too_many_arguments)]
+pub fn animate_enter(
+    mut commands: Commands,
+    time: Res<Time>,
+    settings: Res<Settings>,
+    mut fx: ResMut<BattleTransitionFx>,
+    mut overlay: Query<(Entity, &mut Node, &mut BackgroundColor), With<TransitionOverlay>>,
+    mut camera: Query<(&mut Projection, &mut Transform), With<OverworldCamera>>,
+    enemy_query: Query<&Transform, (With<Enemy>, Without<OverworldCamera>)>,
+    battle_state: Res<CurrentBattle>,
+    mut battle_ui: Query<&mut Visibility, With<BattleUI>>,
+    mut transition: ResMut<NextState<BattleTransition>>,
+) {
+    fx.timer.tick(time.delta());
+    let t = fx.timer.fraction();
+    let Ok((entity, mut node, mut background)) = overlay.single_mut() else { return };
+
+    // Reduced motion swaps the wipe and zoom for a plain fade
+    if settings.reduce_motion {
+        node.left = Val::Percent(0.0);
+        node.width = Val::Percent(100.0);
+        background.0 = Color::BLACK.with_alpha(1.0 - (t * 2.0 - 1.0).abs());
+    } else if t < 0.5 {
+        node.width = Val::Percent(t * 200.0);
+    } else {
+        node.left = Val::Percent((t - 0.5) * 200.0);
+        node.width = Val::Percent(100.0 - (t - 0.5) * 200.0);
+    }
+
+    // Screen shake has already put the camera at rest this frame, so the pan starts from there
+    if let Ok((mut projection, mut camera_transform)) = camera.single_mut() {
+        let zooming = t < 0.5 && !settings.reduce_motion;
+        if let Projection::Orthographic(ortho) = projection.as_mut() {
+            ortho.scale = if zooming { 1.0 - ENTER_ZOOM * t * 2.0 } else { 1.0 };
+        }
+        if zooming {
+            if let Ok(enemy_transform) = enemy_query.get(battle_state.enemy_entity) {
+                let focus = camera_transform.translation.truncate().lerp(enemy_transform.translation.truncate(), t * 2.0);
+                camera_transform.translation = focus.extend(camera_transform.translation.z);
+            }
+        }
+    }
+
+    if t >= 0.5 && !fx.revealed {
+        fx.revealed = true;
+        commands.run_system_cached(crate::combat::setup_battle);
+        commands.run_system_cached(crate::combat::freeze_camera);
+        for mut visibility in battle_ui.iter_mut() {
+            *visibility = Visibility::Visible;
+        }
+    }
+
+    if fx.timer.is_finished() {
+        commands.entity(entity).despawn();
+        transition.set(BattleTransition::Active);
+    }
+}
+
+/// Leaves the fight once combat reports how it ended.
+pub fn begin_exit(
+    mut ended: MessageReader<BattleEnded>,
+    mut fx: ResMut<BattleTransitionFx>,
+    mut transition: ResMut<NextState<BattleTransition>>,
+) {
+    let Some(event) = ended.read().last() else { return };
+    fx.exit_to = match event.outcome {
+        BattleOutcome::Lost => GameState::GameOver,
+        BattleOutcome::Won | BattleOutcome::Fled | BattleOutcome::Spared => GameState::Overworld,
+    };
+    fx.outcome = event.outcome;
+    transition.set(BattleTransition::Exiting);
+}
+
+pub fn start_exit(
+    mut commands: Commands,
+    mut fx: ResMut<BattleTransitionFx>,
+    battle_state: Res<CurrentBattle>,
+    stats: Res<BattleStats>,
+    enemy_query: Query<&Enemy>,
+) {
+    fx.timer = Timer::from_seconds(EXIT_SECS, TimerMode::Once);
+    fx.await_confirm = false;
+
+    let name = enemy_query.get(battle_state.enemy_entity).map(|e| e.name).unwrap_or("Enemy");
+    let (title, color) = match (fx.exit_to, &stats.result) {
+        (GameState::GameOver, _) => ("DEFEATED".to_string(), Color::srgb(1.0, 0.3, 0.3)),
+        (_, Some(result)) => {
+            fx.await_confirm = true;
+            let text = format!(
+                "VICTORY!\n{} defeated\n\nGrade: {}\nTurns: {}   Time: {}\nDamage dealt: {}   Damage taken: {}\nPerfect hits: {}   Grazes: {}\n\n+{} XP\nLoot: {}\n\n[SPACE] Continue",
+                name,
+                result.grade.letter(),
+                stats.turns,
+                format_time(stats.elapsed),
+                stats.damage_dealt,
+                stats.damage_taken,
+                stats.perfects,
+                stats.grazes,
+                result.xp,
+                result.loot.join(", "),
+            );
+            (text, Color::srgb(1.0, 0.9, 0.3))
+        }
+        _ if fx.outcome == BattleOutcome::Spared => (format!("SPARED\n{} lets you pass", name), Color::srgb(1.0, 0.8, 0.9)),
+        _ => (format!("ESCAPED\nfrom {}", name), Color::srgb(0.7, 0.8, 1.0)),
+    };
+
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(Color::BLACK.with_alpha(0.0)),
+            GlobalZIndex(5),
+            TransitionOverlay,
+            BattleSprite,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(title),
+                TextFont { font_size: if fx.await_confirm { 24.0 } else { 40.0 }, ..default() },
+                TextColor(color),
+                TextLayout::new_with_justify(Justify::Center),
+                Visibility::Hidden,
+                ResultsCard,
+            ));
+        });
+}
+
+pub fn animate_exit(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    time: Res<Time>,
+    mut fx: ResMut<BattleTransitionFx>,
+    mut overlay: Query<&mut BackgroundColor, With<TransitionOverlay>>,
+    mut card: Query<&mut Visibility, With<ResultsCard>>,
+    mut game_state: ResMut<NextState<GameState>>,
+) {
+    fx.timer.tick(time.delta());
+    let t = fx.timer.fraction();
+
+    for mut background in overlay.iter_mut() {
+        background.0 = Color::BLACK.with_alpha((t * 3.0).min(1.0) * 0.85);
+    }
+    if t > 0.25 {
+        for mut visibility in card.iter_mut() {
+            *visibility = Visibility::Inherited;
+        }
+    }
+
+    let confirmed = !fx.await_confirm
+        || keyboard.just_pressed(KeyCode::Space)
+        || keyboard.just_pressed(KeyCode::Enter);
+    if fx.timer.is_finished() && confirmed {
+        game_state.set(fx.exit_to);
+    }
+}
+
+/// Undoes the entry zoom and puts the camera back on the player.
+pub fn reset_camera_zoom(
+    mut camera: Query<(&mut Projection, &mut Transform), With<OverworldCamera>>,
+    player_query: Query<&Transform, (With<Player>, Without<OverworldCamera>)>,
+) {
+    let Ok((mut projection, mut camera_transform)) = camera.single_mut() else { return };
+    if let Projection::Orthographic(ortho) = projection.as_mut() {
+        ortho.scale = 1.0;
+    }
+    if let Ok(player_transform) = player_query.single() {
+        camera_transform.translation.x = 0.0;
+        camera_transform.translation.y = player_transform.translation.y;
+    }
+}